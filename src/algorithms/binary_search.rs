@@ -104,6 +104,29 @@ impl<T: PartialOrd> ElementBisect<T> for [T] {
     }
 }
 
+#[snippet("isqrt", include = "binary_search")]
+/// Largest `x` satisfying `x * x <= n`.
+pub fn isqrt(n: u64) -> u64 {
+    nth_root(n, 2)
+}
+
+#[snippet("isqrt", include = "binary_search")]
+/// Largest `x` satisfying `x.pow(k) <= n`.
+///
+/// Panics if `k == 0`.
+pub fn nth_root(n: u64, k: u32) -> u64 {
+    assert!(k > 0);
+    if k == 1 {
+        return n;
+    }
+    // `x.pow(k)` overflows for every `x >= 1 << 32` as `k >= 2`.
+    let f = |x: u64| match x.checked_pow(k) {
+        Some(v) => v <= n,
+        None => false,
+    };
+    f.binary_search(0, 1 << 32, None).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,4 +211,34 @@ mod tests {
         assert_eq!(li.bisect_left(&2.0), 2);
         assert_eq!(li.bisect_right(&2.0), 4);
     }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(17), 4);
+        assert_eq!(isqrt(!0), (1 << 32) - 1);
+        assert_eq!(isqrt(((1 << 32) - 1) * ((1 << 32) - 1)), (1 << 32) - 1);
+        assert_eq!(isqrt(((1 << 32) - 1) * ((1 << 32) - 1) - 1), (1 << 32) - 2);
+    }
+
+    #[test]
+    fn test_nth_root() {
+        assert_eq!(nth_root(27, 3), 3);
+        assert_eq!(nth_root(26, 3), 2);
+        assert_eq!(nth_root(28, 3), 3);
+        assert_eq!(nth_root(0, 5), 0);
+        assert_eq!(nth_root(!0, 1), !0);
+        assert_eq!(nth_root(!0, 3), 2_642_245);
+        assert_eq!(nth_root(!0, 64), 1);
+        assert_eq!(nth_root(1 << 63, 63), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_nth_root_panics_with_zero_exponent() {
+        nth_root(10, 0);
+    }
 }
@@ -0,0 +1,122 @@
+use cargo_snippet::snippet;
+
+#[snippet("bipartite_matching")]
+/// Maximum bipartite matching by Kuhn's augmenting path algorithm
+/// with time-complexity `O(VE)`.
+///
+/// * `match_left`: `match_left[u]` is the right vertex matched with left vertex `u`.
+/// * `match_right`: `match_right[v]` is the left vertex matched with right vertex `v`.
+pub struct BipartiteMatching {
+    adj: Vec<Vec<usize>>,
+    pub match_left: Vec<Option<usize>>,
+    pub match_right: Vec<Option<usize>>,
+}
+
+#[snippet("bipartite_matching")]
+impl BipartiteMatching {
+    pub fn new(left: usize, right: usize) -> Self {
+        Self {
+            adj: vec![vec![]; left],
+            match_left: vec![None; left],
+            match_right: vec![None; right],
+        }
+    }
+
+    /// Add edge between left vertex `u` and right vertex `v`.
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert!(u < self.match_left.len() && v < self.match_right.len());
+        self.adj[u].push(v);
+    }
+
+    fn augment(&mut self, u: usize, visited: &mut [bool]) -> bool {
+        for i in 0..self.adj[u].len() {
+            let v = self.adj[u][i];
+            if visited[v] {
+                continue;
+            }
+            visited[v] = true;
+            let free = match self.match_right[v] {
+                Some(w) => self.augment(w, visited),
+                None => true,
+            };
+            if free {
+                self.match_left[u] = Some(v);
+                self.match_right[v] = Some(u);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Compute maximum matching and returns its size.
+    pub fn solve(&mut self) -> usize {
+        let mut visited = vec![false; self.match_right.len()];
+        for u in 0..self.adj.len() {
+            if self.match_left[u].is_some() {
+                continue;
+            }
+            for x in visited.iter_mut() {
+                *x = false;
+            }
+            self.augment(u, &mut visited);
+        }
+        self.match_left.iter().filter(|m| m.is_some()).count()
+    }
+
+    /// Matched pairs of (left vertex, right vertex) in increasing order of left vertex.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        self.match_left
+            .iter()
+            .enumerate()
+            .filter_map(|(u, &m)| m.map(|v| (u, v)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_graph() {
+        // Left 0 and 1 both can only take right 0.
+        let mut m = BipartiteMatching::new(4, 4);
+        for &(u, v) in &[(0, 0), (1, 0), (2, 1), (2, 2), (3, 2), (3, 3)] {
+            m.add_edge(u, v);
+        }
+        assert_eq!(m.solve(), 3);
+        let pairs = m.pairs();
+        assert_eq!(pairs.len(), 3);
+        for &(u, v) in &pairs {
+            assert_eq!(m.match_right[v], Some(u));
+        }
+    }
+
+    #[test]
+    fn test_requires_augmenting_path() {
+        let mut m = BipartiteMatching::new(3, 3);
+        for &(u, v) in &[(0, 0), (0, 1), (1, 0), (2, 1), (2, 2)] {
+            m.add_edge(u, v);
+        }
+        assert_eq!(m.solve(), 3);
+        assert_eq!(m.pairs(), vec![(0, 1), (1, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn test_complete_bipartite() {
+        let mut m = BipartiteMatching::new(3, 3);
+        for u in 0..3 {
+            for v in 0..3 {
+                m.add_edge(u, v);
+            }
+        }
+        assert_eq!(m.solve(), 3);
+    }
+
+    #[test]
+    fn test_no_edges() {
+        let mut m = BipartiteMatching::new(2, 3);
+        assert_eq!(m.solve(), 0);
+        assert!(m.pairs().is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use cargo_snippet::snippet;
+
+#[snippet("bridge_tree")]
+/// Contract every 2-edge-connected component of an undirected graph into a single node.
+/// Bridges become the edges of the resulting forest.
+///
+/// # Returns
+///
+/// * Number of nodes of the bridge tree.
+/// * Adjacency list of the bridge tree.
+/// * Mapping from original nodes to bridge tree nodes.
+pub fn bridge_tree(
+    n: usize,
+    undirected_edges: &[(usize, usize)],
+) -> (usize, Vec<Vec<usize>>, Vec<usize>) {
+    let mut adj = vec![vec![]; n];
+    for (id, &(u, v)) in undirected_edges.iter().enumerate() {
+        adj[u].push((v, id));
+        adj[v].push((u, id));
+    }
+
+    // Lowlink by iterative DFS. Parallel edges are told apart by their ids.
+    let mut ord = vec![!0; n];
+    let mut low = vec![0; n];
+    let mut is_bridge = vec![false; undirected_edges.len()];
+    let mut cnt = 0;
+    for s in 0..n {
+        if ord[s] != !0 {
+            continue;
+        }
+        ord[s] = cnt;
+        low[s] = cnt;
+        cnt += 1;
+        // (vertex, id of edge to parent, index of next edge to visit)
+        let mut stack = vec![(s, !0, 0)];
+        while let Some(&mut (v, pe, ref mut i)) = stack.last_mut() {
+            if *i < adj[v].len() {
+                let (u, id) = adj[v][*i];
+                *i += 1;
+                if id == pe {
+                    continue;
+                }
+                if ord[u] == !0 {
+                    ord[u] = cnt;
+                    low[u] = cnt;
+                    cnt += 1;
+                    stack.push((u, id, 0));
+                } else {
+                    low[v] = low[v].min(ord[u]);
+                }
+            } else {
+                stack.pop();
+                if let Some(&(p, _, _)) = stack.last() {
+                    low[p] = low[p].min(low[v]);
+                    if low[v] > ord[p] {
+                        is_bridge[pe] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut comp = vec![!0; n];
+    let mut k = 0;
+    for s in 0..n {
+        if comp[s] != !0 {
+            continue;
+        }
+        comp[s] = k;
+        let mut stack = vec![s];
+        while let Some(v) = stack.pop() {
+            for &(u, id) in &adj[v] {
+                if !is_bridge[id] && comp[u] == !0 {
+                    comp[u] = k;
+                    stack.push(u);
+                }
+            }
+        }
+        k += 1;
+    }
+
+    let mut tree = vec![vec![]; k];
+    for (id, &(u, v)) in undirected_edges.iter().enumerate() {
+        if is_bridge[id] {
+            tree[comp[u]].push(comp[v]);
+            tree[comp[v]].push(comp[u]);
+        }
+    }
+    (k, tree, comp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let (k, tree, comp) = bridge_tree(4, &[(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(k, 4);
+        assert_eq!(comp, vec![0, 1, 2, 3]);
+        assert_eq!(tree, vec![vec![1], vec![0, 2], vec![1, 3], vec![2]]);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let (k, tree, comp) = bridge_tree(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert_eq!(k, 1);
+        assert_eq!(comp, vec![0; 4]);
+        assert_eq!(tree, vec![vec![]]);
+    }
+
+    #[test]
+    fn test_two_cycles_with_bridge() {
+        // 0-1-2-0 and 4-5-6-4 linked through 2-3-4
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 4),
+        ];
+        let (k, tree, comp) = bridge_tree(7, &edges);
+        assert_eq!(k, 3);
+        assert!(comp[0] == comp[1] && comp[1] == comp[2]);
+        assert!(comp[4] == comp[5] && comp[5] == comp[6]);
+        assert_eq!(tree[comp[3]].len(), 2);
+        assert_eq!(tree[comp[0]], vec![comp[3]]);
+        assert_eq!(tree[comp[4]], vec![comp[3]]);
+    }
+
+    #[test]
+    fn test_parallel_edges_are_not_bridges() {
+        let (k, tree, _) = bridge_tree(3, &[(0, 1), (0, 1), (1, 2)]);
+        assert_eq!(k, 2);
+        assert_eq!(tree, vec![vec![1], vec![0]]);
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let (k, tree, comp) = bridge_tree(3, &[(1, 2)]);
+        assert_eq!(k, 3);
+        assert_eq!(comp, vec![0, 1, 2]);
+        assert_eq!(tree, vec![vec![], vec![2], vec![1]]);
+    }
+}
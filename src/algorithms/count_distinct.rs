@@ -0,0 +1,74 @@
+use crate::data_structure::segment_tree::SegmentTree;
+use cargo_snippet::snippet;
+
+#[snippet("count_distinct_in_ranges", include = "segment_tree")]
+/// Count distinct values of `a` within each range [`left`, `right`) of `queries`
+/// offline with time-complexity `O((n + q) log n)`.
+///
+/// Queries are answered in increasing order of `right`
+/// while only the last occurrence of each value seen so far is marked in a segment tree.
+pub fn count_distinct_in_ranges(a: &[u64], queries: &[(usize, usize)]) -> Vec<usize> {
+    let mut order = (0..queries.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| queries[i].1);
+
+    let mut tree = SegmentTree::new(a.len(), |x, y| x + y, || 0);
+    let mut last = std::collections::HashMap::new();
+    let mut res = vec![0; queries.len()];
+    let mut r = 0;
+    for i in order {
+        let (left, right) = queries[i];
+        assert!(left <= right && right <= a.len());
+        while r < right {
+            if let Some(prev) = last.insert(a[r], r) {
+                tree.update(prev, 0);
+            }
+            tree.update(r, 1);
+            r += 1;
+        }
+        res[i] = tree.query(Some(left), Some(right));
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_array, XorShift};
+
+    #[test]
+    fn test_count_distinct_in_ranges() {
+        let a = [1, 1, 2, 1, 3];
+        let queries = [(0, 5), (0, 2), (1, 3), (2, 5), (4, 4)];
+        assert_eq!(count_distinct_in_ranges(&a, &queries), vec![3, 1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_count_distinct_in_ranges_matches_brute_force() {
+        let mut rng = XorShift::new();
+        for n in 1..30 {
+            let a = random_array(&mut rng, n, 6);
+            let queries = (0..50)
+                .map(|_| {
+                    let (l, r) = (rng.below(n + 1), rng.below(n + 1));
+                    (l.min(r), l.max(r))
+                })
+                .collect::<Vec<_>>();
+            let expected = queries
+                .iter()
+                .map(|&(l, r)| {
+                    a[l..r]
+                        .iter()
+                        .collect::<std::collections::HashSet<_>>()
+                        .len()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(count_distinct_in_ranges(&a, &queries), expected);
+        }
+    }
+
+    #[test]
+    fn test_count_distinct_in_ranges_empty() {
+        assert_eq!(count_distinct_in_ranges(&[], &[(0, 0)]), vec![0]);
+        assert!(count_distinct_in_ranges(&[1, 2], &[]).is_empty());
+    }
+}
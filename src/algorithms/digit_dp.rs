@@ -0,0 +1,114 @@
+use cargo_snippet::snippet;
+
+#[snippet("digit_dp")]
+/// State of digit DP scanning decimal digits from the most significant one.
+///
+/// * `position`: Number of digits already fixed.
+/// * `tight`: Whether the fixed digits equal the prefix of the upper bound.
+/// * `sum`: Sum of the fixed digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DpState {
+    pub position: usize,
+    pub tight: bool,
+    pub sum: u32,
+}
+
+#[snippet("digit_dp")]
+/// Count integers in `[0, n]` whose decimal digit sum equals `target_sum`
+/// with time-complexity `O(log n * target_sum * 10)`.
+pub fn count_with_digit_sum(n: u64, target_sum: u32) -> u64 {
+    fn dfs(state: DpState, digits: &[u32], target: u32, memo: &mut Vec<Vec<Option<u64>>>) -> u64 {
+        if state.sum > target {
+            return 0;
+        }
+        if state.position == digits.len() {
+            return (state.sum == target) as u64;
+        }
+        if !state.tight {
+            if let Some(res) = memo[state.position][state.sum as usize] {
+                return res;
+            }
+        }
+        let limit = if state.tight {
+            digits[state.position]
+        } else {
+            9
+        };
+        let mut res = 0;
+        for d in 0..=limit {
+            let next = DpState {
+                position: state.position + 1,
+                tight: state.tight && d == limit,
+                sum: state.sum + d,
+            };
+            res += dfs(next, digits, target, memo);
+        }
+        if !state.tight {
+            memo[state.position][state.sum as usize] = Some(res);
+        }
+        res
+    }
+
+    let digits = n
+        .to_string()
+        .bytes()
+        .map(|c| (c - b'0') as u32)
+        .collect::<Vec<_>>();
+    if target_sum as usize > 9 * digits.len() {
+        return 0;
+    }
+    let mut memo = vec![vec![None; target_sum as usize + 1]; digits.len()];
+    let init = DpState {
+        position: 0,
+        tight: true,
+        sum: 0,
+    };
+    dfs(init, &digits, target_sum, &mut memo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit_sum(mut x: u64) -> u32 {
+        let mut res = 0;
+        while x > 0 {
+            res += (x % 10) as u32;
+            x /= 10;
+        }
+        res
+    }
+
+    #[test]
+    fn test_count_with_digit_sum_matches_brute_force() {
+        for n in 0..=1_200 {
+            for target_sum in 0..=30 {
+                let expected = (0..=n).filter(|&x| digit_sum(x) == target_sum).count() as u64;
+                assert_eq!(count_with_digit_sum(n, target_sum), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_with_digit_sum_huge_target() {
+        assert_eq!(count_with_digit_sum(10, !0), 0);
+        assert_eq!(count_with_digit_sum(999, 28), 0);
+        assert_eq!(count_with_digit_sum(999, 27), 1);
+    }
+
+    #[test]
+    fn test_count_with_digit_sum_zero() {
+        assert_eq!(count_with_digit_sum(0, 0), 1);
+        assert_eq!(count_with_digit_sum(0, 1), 0);
+        assert_eq!(count_with_digit_sum(1_000_000, 0), 1);
+    }
+
+    #[test]
+    fn test_count_with_digit_sum_large() {
+        // Every integer in [0, 10^18) has 18 digits when zero padded,
+        // so the counts over all digit sums add up to 10^18.
+        let n = 999_999_999_999_999_999;
+        let total = (0..=162).map(|s| count_with_digit_sum(n, s)).sum::<u64>();
+        assert_eq!(total, 1_000_000_000_000_000_000);
+    }
+}
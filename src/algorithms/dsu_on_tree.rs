@@ -0,0 +1,282 @@
+use cargo_snippet::snippet;
+
+#[snippet("dsu_on_tree")]
+/// Aggregate `values` over every subtree of a tree by small-to-large merging.
+///
+/// The accumulator of the heaviest child is reused for its parent and
+/// those of the other children are merged into it by `combine`,
+/// so that each value is merged `O(log n)` times.
+///
+/// # Arguments
+///
+/// * `n` - Number of nodes.
+/// * `adj` - Adjacency list of the tree.
+/// * `root` - Root of the tree.
+/// * `values` - Value of each node.
+/// * `combine` - Merge the latter aggregate into the former one.
+/// * `answer` - Project the aggregate of a subtree to what is stored for it,
+///   e.g. the number of elements of a set. Aggregates themselves are not kept.
+///
+/// # Returns
+///
+/// `answer` of the aggregate of each subtree indexed by node.
+pub fn dsu_on_tree<T: Default, R>(
+    n: usize,
+    adj: &[Vec<usize>],
+    root: usize,
+    values: &[T],
+    combine: impl Fn(&mut T, &T),
+    answer: impl Fn(&T) -> R,
+) -> Vec<R> {
+    let mut parent = vec![!0; n];
+    let mut order = Vec::with_capacity(n);
+    let mut stack = vec![root];
+    parent[root] = root;
+    while let Some(v) = stack.pop() {
+        order.push(v);
+        for &u in &adj[v] {
+            if parent[u] == !0 {
+                parent[u] = v;
+                stack.push(u);
+            }
+        }
+    }
+
+    let mut size = vec![1; n];
+    for &v in order.iter().rev() {
+        if v != root {
+            size[parent[v]] += size[v];
+        }
+    }
+
+    let mut acc: Vec<Option<T>> = (0..n).map(|_| None).collect();
+    let mut res: Vec<Option<R>> = (0..n).map(|_| None).collect();
+    for &v in order.iter().rev() {
+        let children = adj[v].iter().filter(|&&u| u != parent[v]);
+        let heavy = children.clone().max_by_key(|&&u| size[u]);
+        let mut cur = match heavy {
+            Some(&h) => acc[h].take().unwrap(),
+            None => T::default(),
+        };
+        for &u in children {
+            if Some(&u) != heavy {
+                combine(&mut cur, &acc[u].take().unwrap());
+            }
+        }
+        combine(&mut cur, &values[v]);
+        res[v] = Some(answer(&cur));
+        acc[v] = Some(cur);
+    }
+    res.into_iter().map(Option::unwrap).collect()
+}
+
+#[snippet("dsu_on_tree_incremental")]
+/// Answer a query on every subtree of a tree by maintaining a single aggregate `S`
+/// with time-complexity `O(n log n)` calls of the callbacks.
+///
+/// Subtrees of light children are added and then removed,
+/// while that of the heavy child is kept for its parent.
+///
+/// # Arguments
+///
+/// * `n` - Number of nodes.
+/// * `edges` - Edges of the tree.
+/// * `root` - Root of the tree.
+/// * `add` - Add a node to the aggregate.
+/// * `remove` - Remove a node from the aggregate.
+/// * `answer` - Called once for each node `v` while the aggregate consists of
+///   exactly the subtree of `v`.
+pub fn dsu_on_tree_incremental<S: Default>(
+    n: usize,
+    edges: &[(usize, usize)],
+    root: usize,
+    mut add: impl FnMut(usize, &mut S),
+    mut remove: impl FnMut(usize, &mut S),
+    mut answer: impl FnMut(usize, &S),
+) {
+    let mut adj = vec![vec![]; n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    let mut parent = vec![!0; n];
+    let mut order = Vec::with_capacity(n);
+    let mut stack = vec![root];
+    parent[root] = root;
+    while let Some(v) = stack.pop() {
+        order.push(v);
+        for &u in &adj[v] {
+            if parent[u] == !0 {
+                parent[u] = v;
+                stack.push(u);
+            }
+        }
+    }
+    let mut size = vec![1; n];
+    let mut heavy = vec![!0; n];
+    for &v in order.iter().rev() {
+        if v != root {
+            size[parent[v]] += size[v];
+        }
+    }
+    for &v in &order {
+        if v != root && (heavy[parent[v]] == !0 || size[heavy[parent[v]]] < size[v]) {
+            heavy[parent[v]] = v;
+        }
+    }
+
+    // Subtree of `v` is `ver[tin[v]..tin[v] + size[v]]`.
+    let mut tin = vec![0; n];
+    let mut ver = Vec::with_capacity(n);
+    let mut stack = vec![root];
+    while let Some(v) = stack.pop() {
+        tin[v] = ver.len();
+        ver.push(v);
+        stack.extend(adj[v].iter().filter(|&&u| u != parent[v]));
+    }
+
+    let mut state = S::default();
+    // `(v, keep, entered)`
+    let mut stack = vec![(root, true, false)];
+    while let Some((v, keep, entered)) = stack.pop() {
+        let light = adj[v].iter().filter(|&&u| u != parent[v] && u != heavy[v]);
+        if !entered {
+            stack.push((v, keep, true));
+            if heavy[v] != !0 {
+                stack.push((heavy[v], true, false));
+            }
+            stack.extend(light.map(|&u| (u, false, false)));
+            continue;
+        }
+        for &u in light {
+            for &w in &ver[tin[u]..tin[u] + size[u]] {
+                add(w, &mut state);
+            }
+        }
+        add(v, &mut state);
+        answer(v, &state);
+        if !keep {
+            for &w in &ver[tin[v]..tin[v] + size[v]] {
+                remove(w, &mut state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_tree, to_adjacency, XorShift};
+    use std::collections::BTreeSet;
+
+    fn subtree(adj: &[Vec<usize>], v: usize, p: usize) -> Vec<usize> {
+        let mut res = vec![v];
+        for &u in &adj[v] {
+            if u != p {
+                res.extend(subtree(adj, u, v));
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn test_count_distinct() {
+        let n = 100;
+        let adj = to_adjacency(n, &random_tree(&mut XorShift::new(), n));
+        let colors = (0..n).map(|i| i * i % 7).collect::<Vec<_>>();
+        let values = colors
+            .iter()
+            .map(|&c| std::iter::once(c).collect::<BTreeSet<_>>())
+            .collect::<Vec<_>>();
+        let res = dsu_on_tree(
+            n,
+            &adj,
+            0,
+            &values,
+            |a, b| a.extend(b.iter().cloned()),
+            |a| a.len(),
+        );
+        for v in 0..n {
+            let parent = (0..n).find(|&p| adj[v].contains(&p) && p < v).unwrap_or(!0);
+            let expected = subtree(&adj, v, parent)
+                .iter()
+                .map(|&u| colors[u])
+                .collect::<BTreeSet<_>>();
+            assert_eq!(res[v], expected.len());
+        }
+    }
+
+    #[test]
+    fn test_sum_of_depths() {
+        //     0
+        //    / \
+        //   1   2
+        //  / \   \
+        // 3   4   5
+        let adj = vec![
+            vec![1, 2],
+            vec![0, 3, 4],
+            vec![0, 5],
+            vec![1],
+            vec![1],
+            vec![2],
+        ];
+        let depths = [0_u64, 1, 1, 2, 2, 2];
+        let res = dsu_on_tree(6, &adj, 0, &depths, |a, b| *a += *b, |a| *a);
+        assert_eq!(res, vec![8, 5, 3, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_single_node() {
+        let res = dsu_on_tree(1, &[vec![]], 0, &[5], |a: &mut i32, b| *a += *b, |a| *a);
+        assert_eq!(res, vec![5]);
+    }
+
+    #[test]
+    fn test_incremental_count_distinct() {
+        use std::collections::BTreeMap;
+        let n = 100;
+        let adj = to_adjacency(n, &random_tree(&mut XorShift::new(), n));
+        let edges = (0..n)
+            .flat_map(|v| adj[v].iter().filter(move |&&u| u < v).map(move |&u| (u, v)))
+            .collect::<Vec<_>>();
+        let colors = (0..n).map(|i| i * i % 7).collect::<Vec<_>>();
+        let mut res = vec![!0; n];
+        dsu_on_tree_incremental(
+            n,
+            &edges,
+            0,
+            |v, cnt: &mut BTreeMap<usize, usize>| *cnt.entry(colors[v]).or_insert(0) += 1,
+            |v, cnt| {
+                let c = cnt.get_mut(&colors[v]).unwrap();
+                *c -= 1;
+                if *c == 0 {
+                    cnt.remove(&colors[v]);
+                }
+            },
+            |v, cnt| res[v] = cnt.len(),
+        );
+        for v in 0..n {
+            let parent = (0..n).find(|&p| adj[v].contains(&p) && p < v).unwrap_or(!0);
+            let expected = subtree(&adj, v, parent)
+                .iter()
+                .map(|&u| colors[u])
+                .collect::<BTreeSet<_>>();
+            assert_eq!(res[v], expected.len());
+        }
+    }
+
+    #[test]
+    fn test_incremental_single_node() {
+        let mut res = vec![];
+        dsu_on_tree_incremental(
+            1,
+            &[],
+            0,
+            |v, s: &mut usize| *s += v + 1,
+            |v, s| *s -= v + 1,
+            |v, s| res.push((v, *s)),
+        );
+        assert_eq!(res, vec![(0, 1)]);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::algorithms::bipartite_matching::BipartiteMatching;
+use cargo_snippet::snippet;
+
+#[snippet("hungarian")]
+/// Minimum cost assignment of every row of an `n` x `m` matrix `cost` (`n <= m`)
+/// to distinct columns by the Hungarian algorithm with potentials
+/// with time-complexity `O(n^2 m)`.
+///
+/// Returns `(total_cost, assignment)` where row `i` is assigned to column `assignment[i]`.
+/// Columns left unassigned in a rectangular matrix act as padding.
+pub fn hungarian(cost: &[Vec<i64>]) -> (i64, Vec<usize>) {
+    const INF: i64 = 1 << 62;
+    let n = cost.len();
+    if n == 0 {
+        return (0, vec![]);
+    }
+    let m = cost[0].len();
+    assert!(n <= m && cost.iter().all(|row| row.len() == m));
+
+    // 1-indexed potentials; column `0` is a virtual one holding the row being inserted.
+    let mut u = vec![0; n + 1];
+    let mut v = vec![0; m + 1];
+    // `p[j]`: row matched with column `j`, or `0` if none.
+    let mut p = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut min_v = vec![INF; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = INF;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if cur < min_v[j] {
+                    min_v[j] = cur;
+                    way[j] = j0;
+                }
+                if min_v[j] < delta {
+                    delta = min_v[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_v[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        // Flip the augmenting path.
+        while j0 != 0 {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for j in 1..=m {
+        if p[j] != 0 {
+            assignment[p[j] - 1] = j - 1;
+        }
+    }
+    let total = (0..n).map(|i| cost[i][assignment[i]]).sum();
+    (total, assignment)
+}
+
+#[snippet("hungarian")]
+/// Maximum weight assignment of every row of an `n` x `m` matrix `weight` (`n <= m`)
+/// to distinct columns, by `hungarian` on negated weights.
+///
+/// Returns `(total_weight, assignment)` where row `i` is assigned to column `assignment[i]`.
+pub fn weighted_bipartite_matching(weight: &[Vec<i64>]) -> (i64, Vec<usize>) {
+    let negated = weight
+        .iter()
+        .map(|row| row.iter().map(|&w| -w).collect())
+        .collect::<Vec<Vec<i64>>>();
+    let (total, assignment) = hungarian(&negated);
+    (-total, assignment)
+}
+
+#[snippet("is_perfect_matching", include = "bipartite_matching")]
+/// Tests if every row can be matched to a distinct column,
+/// where cell `(i, j)` is an edge if and only if `is_edge(&cost[i][j])`.
+///
+/// Unlike `hungarian`, which may assign any cell, absent edges must be spelled out,
+/// e.g. `Option::is_some` on `Option` costs or `|&c| c != 0` if `0` marks an absent edge.
+pub fn is_perfect_matching<T, F: Fn(&T) -> bool>(cost: &[Vec<T>], is_edge: F) -> bool {
+    let n = cost.len();
+    let m = cost.first().map_or(0, |row| row.len());
+    let mut matching = BipartiteMatching::new(n, m);
+    for (i, row) in cost.iter().enumerate() {
+        for (j, c) in row.iter().enumerate() {
+            if is_edge(c) {
+                matching.add_edge(i, j);
+            }
+        }
+    }
+    matching.solve() == n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    fn brute_force(cost: &[Vec<i64>]) -> i64 {
+        fn dfs(cost: &[Vec<i64>], i: usize, used: &mut Vec<bool>) -> i64 {
+            if i == cost.len() {
+                return 0;
+            }
+            let mut best = std::i64::MAX;
+            for j in 0..used.len() {
+                if !used[j] {
+                    used[j] = true;
+                    best = best.min(cost[i][j] + dfs(cost, i + 1, used));
+                    used[j] = false;
+                }
+            }
+            best
+        }
+        dfs(cost, 0, &mut vec![false; cost[0].len()])
+    }
+
+    fn is_injective(assignment: &[usize]) -> bool {
+        let mut cols = assignment.to_vec();
+        cols.sort_unstable();
+        cols.dedup();
+        cols.len() == assignment.len()
+    }
+
+    #[test]
+    fn test_identity_cost() {
+        let cost = vec![vec![1, 5, 5], vec![5, 1, 5], vec![5, 5, 1]];
+        assert_eq!(hungarian(&cost), (3, vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_textbook() {
+        let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        let (total, assignment) = hungarian(&cost);
+        assert_eq!(total, 5);
+        assert_eq!(assignment, vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_rectangular_against_brute_force() {
+        let mut rng = XorShift::new();
+        for n in 1..=4 {
+            for m in n..=6 {
+                let cost = (0..n)
+                    .map(|_| (0..m).map(|_| rng.below(41) as i64 - 20).collect())
+                    .collect::<Vec<Vec<i64>>>();
+                let (total, assignment) = hungarian(&cost);
+                assert!(is_injective(&assignment));
+                let sum = (0..n).map(|i| cost[i][assignment[i]]).sum::<i64>();
+                assert_eq!(total, sum);
+                assert_eq!(total, brute_force(&cost));
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(hungarian(&[]), (0, vec![]));
+    }
+
+    #[test]
+    fn test_weighted_bipartite_matching() {
+        let weight = vec![vec![1, 2], vec![3, 5]];
+        assert_eq!(hungarian(&weight), (5, vec![1, 0]));
+        assert_eq!(weighted_bipartite_matching(&weight), (6, vec![0, 1]));
+    }
+
+    #[test]
+    fn test_weighted_bipartite_matching_rectangular() {
+        let weight = vec![vec![1, 9, 3, 4], vec![8, 9, 2, 7]];
+        let (total, assignment) = weighted_bipartite_matching(&weight);
+        assert_eq!(total, 17);
+        assert_eq!(assignment, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_weighted_bipartite_matching_all_zero() {
+        let weight = vec![vec![0; 3]; 3];
+        let (total, assignment) = weighted_bipartite_matching(&weight);
+        assert_eq!(total, 0);
+        assert!(is_injective(&assignment));
+    }
+
+    #[test]
+    fn test_is_perfect_matching() {
+        let nonzero = |&c: &i64| c != 0;
+        assert!(is_perfect_matching(&[vec![1, 0], vec![0, 1]], nonzero));
+        assert!(!is_perfect_matching(&[vec![1, 0], vec![2, 0]], nonzero));
+        assert!(is_perfect_matching(
+            &[vec![0, 0, 3], vec![4, 0, 0]],
+            nonzero
+        ));
+        assert!(!is_perfect_matching(&vec![vec![0; 3]; 3], nonzero));
+        assert!(is_perfect_matching(&vec![vec![0; 3]; 3], |_| true));
+    }
+
+    #[test]
+    fn test_is_perfect_matching_with_option_costs() {
+        let cost = vec![vec![Some(0), None], vec![Some(5), None]];
+        assert!(!is_perfect_matching(&cost, Option::is_some));
+        let cost = vec![vec![None, Some(0)], vec![Some(0), None]];
+        assert!(is_perfect_matching(&cost, Option::is_some));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::data_structure::segment_tree::SegmentTree;
+use cargo_snippet::snippet;
+
+#[snippet("max_subarray_sum")]
+/// Maximum sum of a non-empty contiguous subarray by Kadane's algorithm
+/// with time-complexity `O(n)`.
+///
+/// If every element is negative, the largest single element is returned.
+/// Returns `T::default()` for empty `a`.
+pub fn max_subarray_sum<T>(a: &[T]) -> T
+where
+    T: Copy + Ord + std::ops::Add<Output = T> + Default,
+{
+    let mut iter = a.iter();
+    let first = match iter.next() {
+        Some(&x) => x,
+        None => return T::default(),
+    };
+    let (mut best, mut cur) = (first, first);
+    for &x in iter {
+        cur = x.max(cur + x);
+        best = best.max(cur);
+    }
+    best
+}
+
+#[snippet("max_subarray_seg_tree", include = "segment_tree")]
+#[derive(Debug, Clone, Copy)]
+pub struct MaxSubarrayNode {
+    prefix_max: i64,
+    suffix_max: i64,
+    total: i64,
+    max_sum: i64,
+}
+
+#[snippet("max_subarray_seg_tree", include = "segment_tree")]
+type MaxSubarrayOp =
+    fn(&Option<MaxSubarrayNode>, &Option<MaxSubarrayNode>) -> Option<MaxSubarrayNode>;
+
+#[snippet("max_subarray_seg_tree", include = "segment_tree")]
+/// Segment tree for maximum sum of a non-empty contiguous subarray within a range
+/// with point updates.
+pub struct MaxSubarraySegTree {
+    tree: SegmentTree<Option<MaxSubarrayNode>, MaxSubarrayOp, fn() -> Option<MaxSubarrayNode>>,
+}
+
+#[snippet("max_subarray_seg_tree", include = "segment_tree")]
+impl MaxSubarraySegTree {
+    fn leaf(x: i64) -> Option<MaxSubarrayNode> {
+        Some(MaxSubarrayNode {
+            prefix_max: x,
+            suffix_max: x,
+            total: x,
+            max_sum: x,
+        })
+    }
+
+    fn op(a: &Option<MaxSubarrayNode>, b: &Option<MaxSubarrayNode>) -> Option<MaxSubarrayNode> {
+        match (*a, *b) {
+            (Some(a), Some(b)) => Some(MaxSubarrayNode {
+                prefix_max: a.prefix_max.max(a.total + b.prefix_max),
+                suffix_max: b.suffix_max.max(b.total + a.suffix_max),
+                total: a.total + b.total,
+                max_sum: a.max_sum.max(b.max_sum).max(a.suffix_max + b.prefix_max),
+            }),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+
+    pub fn from_slice(a: &[i64]) -> Self {
+        let leaves = a.iter().map(|&x| Self::leaf(x)).collect::<Vec<_>>();
+        Self {
+            tree: SegmentTree::from_slice(&leaves, Self::op as MaxSubarrayOp, || None),
+        }
+    }
+
+    /// Update value for `i`th element.
+    pub fn update(&mut self, i: usize, x: i64) {
+        self.tree.update(i, Self::leaf(x));
+    }
+
+    /// Maximum subarray sum within range [`left`, `right`). Panics if the range is empty.
+    pub fn query(&self, left: usize, right: usize) -> i64 {
+        assert!(left < right);
+        self.tree.query(Some(left), Some(right)).unwrap().max_sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_subarray_sum() {
+        assert_eq!(max_subarray_sum(&[-2, 1, -3, 4, -1, 2, 1, -5, 4]), 6);
+        assert_eq!(max_subarray_sum(&[5]), 5);
+    }
+
+    #[test]
+    fn test_max_subarray_sum_all_negative() {
+        assert_eq!(max_subarray_sum(&[-3, -1, -2]), -1);
+    }
+
+    #[test]
+    fn test_max_subarray_sum_empty() {
+        let a: [i64; 0] = [];
+        assert_eq!(max_subarray_sum(&a), 0);
+    }
+
+    #[test]
+    fn test_seg_tree_query() {
+        let a = [-2, 1, -3, 4, -1, 2, 1, -5, 4];
+        let t = MaxSubarraySegTree::from_slice(&a);
+        for l in 0..a.len() {
+            for r in l + 1..=a.len() {
+                assert_eq!(t.query(l, r), max_subarray_sum(&a[l..r]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_seg_tree_update() {
+        let mut a = vec![-2, 1, -3, 4, -1, 2, 1, -5, 4];
+        let mut t = MaxSubarraySegTree::from_slice(&a);
+        for &(i, x) in &[(7, 10), (3, -10), (0, 3), (8, -1)] {
+            a[i] = x;
+            t.update(i, x);
+            assert_eq!(t.query(0, a.len()), max_subarray_sum(&a));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_seg_tree_empty_query() {
+        let t = MaxSubarraySegTree::from_slice(&[1, 2, 3]);
+        t.query(1, 1);
+    }
+}
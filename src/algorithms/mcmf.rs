@@ -0,0 +1,199 @@
+use cargo_snippet::snippet;
+
+#[snippet("mcmf")]
+#[derive(Debug, Clone)]
+struct McmfEdge {
+    to: usize,
+    cap: u64,
+    cost: i64,
+    rev: usize,
+}
+
+#[snippet("mcmf")]
+/// Minimum cost flow by successive shortest paths (primal-dual)
+/// with time-complexity `O(F (E log V))` where `F` is the amount of flow.
+///
+/// Shortest paths are found by Dijkstra's algorithm on costs reduced by potentials.
+/// Negative costs are allowed as long as there is no negative cycle,
+/// in which case the initial potentials are computed by Bellman-Ford.
+#[derive(Debug, Clone)]
+pub struct MinCostFlow {
+    graph: Vec<Vec<McmfEdge>>,
+    pos: Vec<(usize, usize)>,
+}
+
+#[snippet("mcmf")]
+impl MinCostFlow {
+    pub fn new(n: usize) -> Self {
+        Self {
+            graph: vec![vec![]; n],
+            pos: vec![],
+        }
+    }
+
+    /// Add directed edge and returns its id.
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: u64, cost: i64) -> usize {
+        assert!(from < self.graph.len() && to < self.graph.len());
+        let id = self.pos.len();
+        let (i, j) = (self.graph[from].len(), self.graph[to].len());
+        let j = if from == to { j + 1 } else { j };
+        self.pos.push((from, i));
+        self.graph[from].push(McmfEdge {
+            to,
+            cap,
+            cost,
+            rev: j,
+        });
+        self.graph[to].push(McmfEdge {
+            to: from,
+            cap: 0,
+            cost: -cost,
+            rev: i,
+        });
+        id
+    }
+
+    /// Amount of flow through the edge of `id`.
+    pub fn edge_flow(&self, id: usize) -> u64 {
+        let (v, i) = self.pos[id];
+        let e = &self.graph[v][i];
+        self.graph[e.to][e.rev].cap
+    }
+
+    /// Flow at most `max_flow` from `src` to `sink` with minimum cost
+    /// and returns the amount of flow and its cost.
+    pub fn flow(&mut self, src: usize, sink: usize, max_flow: u64) -> (u64, i64) {
+        const INF: i64 = 1 << 62;
+        let n = self.graph.len();
+        let mut potential = vec![0; n];
+        if self.graph.iter().flatten().any(|e| e.cap > 0 && e.cost < 0) {
+            // Bellman-Ford from `src`.
+            let mut dist = vec![INF; n];
+            dist[src] = 0;
+            for _ in 0..n {
+                let mut updated = false;
+                for v in 0..n {
+                    if dist[v] == INF {
+                        continue;
+                    }
+                    for e in &self.graph[v] {
+                        if e.cap > 0 && dist[v] + e.cost < dist[e.to] {
+                            dist[e.to] = dist[v] + e.cost;
+                            updated = true;
+                        }
+                    }
+                }
+                if !updated {
+                    break;
+                }
+            }
+            for v in 0..n {
+                if dist[v] != INF {
+                    potential[v] = dist[v];
+                }
+            }
+        }
+
+        let (mut flow, mut cost) = (0, 0);
+        let mut prev = vec![(!0, !0); n];
+        while flow < max_flow {
+            let mut dist = vec![INF; n];
+            let mut heap = std::collections::BinaryHeap::new();
+            dist[src] = 0;
+            heap.push(std::cmp::Reverse((0, src)));
+            while let Some(std::cmp::Reverse((d, v))) = heap.pop() {
+                if dist[v] < d {
+                    continue;
+                }
+                for (i, e) in self.graph[v].iter().enumerate() {
+                    let nd = d + e.cost + potential[v] - potential[e.to];
+                    if e.cap > 0 && nd < dist[e.to] {
+                        dist[e.to] = nd;
+                        prev[e.to] = (v, i);
+                        heap.push(std::cmp::Reverse((nd, e.to)));
+                    }
+                }
+            }
+            if dist[sink] == INF {
+                break;
+            }
+            for v in 0..n {
+                if dist[v] != INF {
+                    potential[v] += dist[v];
+                }
+            }
+            let mut f = max_flow - flow;
+            let mut v = sink;
+            while v != src {
+                let (u, i) = prev[v];
+                f = f.min(self.graph[u][i].cap);
+                v = u;
+            }
+            let mut v = sink;
+            while v != src {
+                let (u, i) = prev[v];
+                self.graph[u][i].cap -= f;
+                let rev = self.graph[u][i].rev;
+                self.graph[v][rev].cap += f;
+                v = u;
+            }
+            flow += f;
+            cost += f as i64 * (potential[sink] - potential[src]);
+        }
+        (flow, cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> MinCostFlow {
+        //      (cap, cost)
+        // 0 -(2,1)-> 1 -(1,1)-> 3
+        // 0 -(1,2)-> 2 -(2,1)-> 3
+        //            1 -(1,3)-> 2
+        let mut g = MinCostFlow::new(4);
+        g.add_edge(0, 1, 2, 1);
+        g.add_edge(0, 2, 1, 2);
+        g.add_edge(1, 3, 1, 1);
+        g.add_edge(2, 3, 2, 1);
+        g.add_edge(1, 2, 1, 3);
+        g
+    }
+
+    #[test]
+    fn test_min_cost_max_flow() {
+        let mut g = sample();
+        // Paths 0-1-3 (2), 0-2-3 (3) and 0-1-2-3 (5)
+        assert_eq!(g.flow(0, 3, 10), (3, 10));
+        assert_eq!(g.edge_flow(0), 2);
+        assert_eq!(g.edge_flow(4), 1);
+    }
+
+    #[test]
+    fn test_partial_flow() {
+        let mut g = sample();
+        assert_eq!(g.flow(0, 3, 2), (2, 5));
+        let mut g = sample();
+        assert_eq!(g.flow(0, 3, 0), (0, 0));
+    }
+
+    #[test]
+    fn test_negative_cost() {
+        let mut g = MinCostFlow::new(4);
+        g.add_edge(0, 1, 1, -5);
+        g.add_edge(0, 2, 1, 1);
+        g.add_edge(1, 3, 1, 2);
+        g.add_edge(2, 3, 1, 2);
+        assert_eq!(g.flow(0, 3, 1), (1, -3));
+        assert_eq!(g.flow(0, 3, 5), (1, 3));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let mut g = MinCostFlow::new(3);
+        g.add_edge(0, 1, 5, 1);
+        assert_eq!(g.flow(0, 2, 5), (0, 0));
+    }
+}
@@ -0,0 +1,113 @@
+use cargo_snippet::snippet;
+
+#[snippet("memoize")]
+/// Return the cached value for `key`, or evaluate `compute` and cache its result.
+///
+/// `compute` receives `cache` itself so that it can recurse into smaller keys.
+pub fn memoized<K, V, F>(cache: &mut std::collections::HashMap<K, V>, key: K, compute: F) -> V
+where
+    K: std::hash::Hash + Eq,
+    V: Clone,
+    F: FnOnce(&mut std::collections::HashMap<K, V>) -> V,
+{
+    if let Some(v) = cache.get(&key) {
+        return v.clone();
+    }
+    let v = compute(cache);
+    cache.insert(key, v.clone());
+    v
+}
+
+#[snippet("memoize")]
+/// Cache of a recursion keyed by `K`.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: std::collections::HashMap<K, V>,
+}
+
+#[snippet("memoize")]
+impl<K: std::hash::Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: std::collections::HashMap::new(),
+        }
+    }
+}
+
+#[snippet("memoize")]
+impl<K: std::hash::Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Return the cached value for `key`, or evaluate `compute` which may recurse through `self`.
+    pub fn get_or_compute<F: FnOnce(&mut Self) -> V>(&mut self, key: K, compute: F) -> V {
+        if let Some(v) = self.cache.get(&key) {
+            return v.clone();
+        }
+        let v = compute(self);
+        self.cache.insert(key, v.clone());
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn fib(n: u64, cache: &mut HashMap<u64, u64>, calls: &mut HashMap<u64, usize>) -> u64 {
+        memoized(cache, n, |cache| {
+            *calls.entry(n).or_insert(0) += 1;
+            if n < 2 {
+                n
+            } else {
+                fib(n - 1, cache, calls) + fib(n - 2, cache, calls)
+            }
+        })
+    }
+
+    #[test]
+    fn test_memoized_fibonacci() {
+        let mut cache = HashMap::new();
+        let mut calls = HashMap::new();
+        assert_eq!(fib(90, &mut cache, &mut calls), 2_880_067_194_370_816_120);
+        assert_eq!(calls.len(), 91);
+        assert!(calls.values().all(|&c| c == 1));
+        assert_eq!(fib(50, &mut cache, &mut calls), 12_586_269_025);
+        assert!(calls.values().all(|&c| c == 1));
+    }
+
+    fn paths(memo: &mut Memo<(usize, usize), u64>, i: usize, j: usize, calls: &mut usize) -> u64 {
+        memo.get_or_compute((i, j), |memo| {
+            *calls += 1;
+            if i == 0 || j == 0 {
+                1
+            } else {
+                paths(memo, i - 1, j, calls) + paths(memo, i, j - 1, calls)
+            }
+        })
+    }
+
+    #[test]
+    fn test_memo_grid_paths() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+        assert_eq!(paths(&mut memo, 10, 10, &mut calls), 184_756);
+        assert_eq!(calls, memo.len());
+        assert_eq!(memo.get(&(2, 2)), Some(&6));
+        assert_eq!(memo.get(&(11, 0)), None);
+    }
+}
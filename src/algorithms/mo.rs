@@ -0,0 +1,131 @@
+use cargo_snippet::snippet;
+
+#[snippet("mo")]
+/// Order to process `queries` of ranges [`left`, `right`) within `0..n` by Mo's algorithm,
+/// so that moving both ends between consecutive queries takes `O(n sqrt q)` steps in total.
+///
+/// Queries are grouped by blocks of `left`, and `right` goes back and forth between blocks.
+pub fn mos_order(n: usize, queries: &[(usize, usize)]) -> Vec<usize> {
+    let q = queries.len().max(1);
+    let mut width = 1;
+    while width * width * q < n * n {
+        width += 1;
+    }
+    let mut order = (0..queries.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| {
+        let (l, r) = queries[i];
+        let block = l / width;
+        (block, if block & 1 == 0 { r } else { !r })
+    });
+    order
+}
+
+#[snippet("range_mode_queries", include = "mo")]
+/// Most frequent value within each range [`left`, `right`) of `queries` offline
+/// by Mo's algorithm with time-complexity `O(n sqrt q log n)`.
+///
+/// Ties are broken by the smallest value. Every range must be nonempty.
+pub fn range_mode_queries(a: &[u64], queries: &[(usize, usize)]) -> Vec<u64> {
+    let mut values = a.to_vec();
+    values.sort_unstable();
+    values.dedup();
+    let id = a
+        .iter()
+        .map(|x| values.binary_search(x).unwrap())
+        .collect::<Vec<_>>();
+
+    // `by_freq[c]`: Values occurring exactly `c` times in the current range.
+    let mut cnt = vec![0; values.len()];
+    let mut by_freq = vec![std::collections::BTreeSet::new(); a.len() + 1];
+    let mut max_freq = 0;
+    let mut res = vec![0; queries.len()];
+    let (mut l, mut r) = (0, 0);
+    for i in mos_order(a.len(), queries) {
+        let (left, right) = queries[i];
+        assert!(left < right && right <= a.len());
+        let mut add = |x: usize, by_freq: &mut Vec<std::collections::BTreeSet<usize>>| {
+            by_freq[cnt[x]].remove(&x);
+            cnt[x] += 1;
+            by_freq[cnt[x]].insert(x);
+            max_freq = max_freq.max(cnt[x]);
+        };
+        while l > left {
+            l -= 1;
+            add(id[l], &mut by_freq);
+        }
+        while r < right {
+            add(id[r], &mut by_freq);
+            r += 1;
+        }
+        let mut remove = |x: usize, by_freq: &mut Vec<std::collections::BTreeSet<usize>>| {
+            by_freq[cnt[x]].remove(&x);
+            if cnt[x] == max_freq && by_freq[cnt[x]].is_empty() {
+                max_freq -= 1;
+            }
+            cnt[x] -= 1;
+            by_freq[cnt[x]].insert(x);
+        };
+        while l < left {
+            remove(id[l], &mut by_freq);
+            l += 1;
+        }
+        while r > right {
+            r -= 1;
+            remove(id[r], &mut by_freq);
+        }
+        res[i] = values[*by_freq[max_freq].iter().next().unwrap()];
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    fn brute_force_mode(a: &[u64]) -> u64 {
+        let mut best = (0, 0);
+        for &x in a {
+            let c = a.iter().filter(|&&y| y == x).count();
+            if c > best.0 || (c == best.0 && x < best.1) {
+                best = (c, x);
+            }
+        }
+        best.1
+    }
+
+    #[test]
+    fn test_range_mode_queries() {
+        let a = [3, 1, 3, 2, 2, 1, 2];
+        let queries = [(0, 7), (0, 3), (1, 2), (3, 6), (1, 6), (5, 7)];
+        assert_eq!(range_mode_queries(&a, &queries), vec![2, 3, 1, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_range_mode_queries_against_brute_force() {
+        let mut rng = XorShift::new();
+        for n in 1..=30 {
+            let a = (0..n).map(|_| rng.next_u64() % 5).collect::<Vec<_>>();
+            let queries = (0..50)
+                .map(|_| {
+                    let l = rng.below(n);
+                    let r = l + 1 + rng.below(n - l);
+                    (l, r)
+                })
+                .collect::<Vec<_>>();
+            let expected = queries
+                .iter()
+                .map(|&(l, r)| brute_force_mode(&a[l..r]))
+                .collect::<Vec<_>>();
+            assert_eq!(range_mode_queries(&a, &queries), expected);
+        }
+    }
+
+    #[test]
+    fn test_mos_order_is_permutation() {
+        let queries = [(5, 9), (0, 3), (2, 8), (7, 10), (1, 1)];
+        let mut order = mos_order(10, &queries);
+        order.sort_unstable();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -1 +1,18 @@
 pub mod binary_search;
+pub mod bipartite_matching;
+pub mod bridge_tree;
+pub mod count_distinct;
+pub mod digit_dp;
+pub mod dsu_on_tree;
+pub mod hungarian;
+pub mod max_subarray;
+pub mod mcmf;
+pub mod memoize;
+pub mod mo;
+pub mod monotone_stack;
+pub mod permutation;
+pub mod prefix_extremum;
+pub mod prefix_sum;
+pub mod reroot_dp;
+pub mod traversal;
+pub mod virtual_tree;
@@ -0,0 +1,131 @@
+use cargo_snippet::snippet;
+
+#[snippet("monotone_stack")]
+/// `res[i]` is the index of the nearest element to the left of `a[i]`
+/// which is strictly smaller than `a[i]`, with time-complexity `O(n)`.
+pub fn prev_smaller<T: PartialOrd>(a: &[T]) -> Vec<Option<usize>> {
+    let mut res = vec![None; a.len()];
+    let mut stack: Vec<usize> = vec![];
+    for (i, x) in a.iter().enumerate() {
+        while let Some(&j) = stack.last() {
+            if a[j] < *x {
+                break;
+            }
+            stack.pop();
+        }
+        res[i] = stack.last().cloned();
+        stack.push(i);
+    }
+    res
+}
+
+#[snippet("monotone_stack")]
+/// `res[i]` is the index of the nearest element to the right of `a[i]`
+/// which is strictly greater than `a[i]`, with time-complexity `O(n)`.
+pub fn next_greater<T: PartialOrd>(a: &[T]) -> Vec<Option<usize>> {
+    let mut res = vec![None; a.len()];
+    let mut stack: Vec<usize> = vec![];
+    for (i, x) in a.iter().enumerate().rev() {
+        while let Some(&j) = stack.last() {
+            if a[j] > *x {
+                break;
+            }
+            stack.pop();
+        }
+        res[i] = stack.last().cloned();
+        stack.push(i);
+    }
+    res
+}
+
+#[snippet("largest_rectangle_in_histogram", include = "monotone_stack")]
+/// Largest area of an axis-aligned rectangle within the histogram of `heights`
+/// whose bars have width `1`, with time-complexity `O(n)`.
+pub fn largest_rectangle_in_histogram(heights: &[u64]) -> u64 {
+    let n = heights.len();
+    let left = prev_smaller(heights);
+    let rev = heights.iter().rev().cloned().collect::<Vec<_>>();
+    let right = prev_smaller(&rev);
+    (0..n)
+        .map(|i| {
+            // Bar `i` extends until strictly lower bars on both sides.
+            let l = left[i].map_or(0, |j| j + 1);
+            let r = right[n - 1 - i].map_or(n, |j| n - 1 - j);
+            heights[i] * (r - l) as u64
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_array, XorShift};
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = XorShift::new();
+        for _ in 0..20 {
+            let a = random_array(&mut rng, 100, 20);
+            let n = a.len();
+            let prev = (0..n)
+                .map(|i| (0..i).rev().find(|&j| a[j] < a[i]))
+                .collect::<Vec<_>>();
+            let next = (0..n)
+                .map(|i| (i + 1..n).find(|&j| a[j] > a[i]))
+                .collect::<Vec<_>>();
+            assert_eq!(prev_smaller(&a), prev);
+            assert_eq!(next_greater(&a), next);
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let a: [i32; 0] = [];
+        assert!(prev_smaller(&a).is_empty());
+        assert!(next_greater(&a).is_empty());
+    }
+
+    #[test]
+    fn test_all_equal() {
+        let a = [7; 5];
+        assert_eq!(prev_smaller(&a), vec![None; 5]);
+        assert_eq!(next_greater(&a), vec![None; 5]);
+    }
+
+    #[test]
+    fn test_strictly_increasing() {
+        let a = [1, 2, 3, 4];
+        assert_eq!(prev_smaller(&a), vec![None, Some(0), Some(1), Some(2)]);
+        assert_eq!(next_greater(&a), vec![Some(1), Some(2), Some(3), None]);
+    }
+
+    #[test]
+    fn test_partial_ord() {
+        let a = [1.5, 0.5, 2.5];
+        assert_eq!(prev_smaller(&a), vec![None, None, Some(1)]);
+        assert_eq!(next_greater(&a), vec![Some(2), Some(2), None]);
+    }
+
+    #[test]
+    fn test_largest_rectangle_in_histogram() {
+        assert_eq!(largest_rectangle_in_histogram(&[2, 1, 5, 6, 2, 3]), 10);
+        assert_eq!(largest_rectangle_in_histogram(&[2, 4]), 4);
+        assert_eq!(largest_rectangle_in_histogram(&[3, 3, 3]), 9);
+        assert_eq!(largest_rectangle_in_histogram(&[]), 0);
+    }
+
+    #[test]
+    fn test_largest_rectangle_matches_brute_force() {
+        let mut rng = XorShift::new();
+        for _ in 0..20 {
+            let h = random_array(&mut rng, 30, 20);
+            let expected = (0..h.len())
+                .flat_map(|l| (l + 1..=h.len()).map(move |r| (l, r)))
+                .map(|(l, r)| h[l..r].iter().min().unwrap() * (r - l) as u64)
+                .max()
+                .unwrap();
+            assert_eq!(largest_rectangle_in_histogram(&h), expected);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use cargo_snippet::snippet;
+
+#[snippet("permutation")]
+/// Lexicographic stepping of permutations like C++ `std::next_permutation`.
+pub trait Permutation {
+    /// Rearrange into the next permutation in lexicographic order and return `true`,
+    /// or sort ascending and return `false` if `self` is the last one.
+    fn next_permutation(&mut self) -> bool;
+    /// Rearrange into the previous permutation in lexicographic order and return `true`,
+    /// or sort descending and return `false` if `self` is the first one.
+    fn prev_permutation(&mut self) -> bool;
+}
+
+#[snippet("permutation")]
+impl<T: Ord> Permutation for [T] {
+    fn next_permutation(&mut self) -> bool {
+        let i = match self.windows(2).rposition(|w| w[0] < w[1]) {
+            Some(i) => i,
+            None => {
+                self.reverse();
+                return false;
+            }
+        };
+        let j = self.iter().rposition(|x| self[i] < *x).unwrap();
+        self.swap(i, j);
+        self[i + 1..].reverse();
+        true
+    }
+
+    fn prev_permutation(&mut self) -> bool {
+        let i = match self.windows(2).rposition(|w| w[0] > w[1]) {
+            Some(i) => i,
+            None => {
+                self.reverse();
+                return false;
+            }
+        };
+        let j = self.iter().rposition(|x| self[i] > *x).unwrap();
+        self.swap(i, j);
+        self[i + 1..].reverse();
+        true
+    }
+}
+
+#[snippet("permutation")]
+/// Distinct permutations of `a` in lexicographic order starting from `a` itself
+/// up to the last one.
+pub fn permutations<T: Ord + Clone>(a: &[T]) -> impl Iterator<Item = Vec<T>> {
+    std::iter::successors(Some(a.to_vec()), |p| {
+        let mut q = p.clone();
+        if q.next_permutation() {
+            Some(q)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permutations_of_sorted() {
+        assert_eq!(
+            permutations(&[1, 2, 3]).collect::<Vec<_>>(),
+            vec![
+                vec![1, 2, 3],
+                vec![1, 3, 2],
+                vec![2, 1, 3],
+                vec![2, 3, 1],
+                vec![3, 1, 2],
+                vec![3, 2, 1],
+            ]
+        );
+        assert_eq!(permutations(&[2, 3, 1]).count(), 3);
+        assert_eq!(permutations::<i32>(&[]).collect::<Vec<_>>(), vec![vec![]]);
+    }
+
+    #[test]
+    fn test_permutations_with_duplicates() {
+        assert_eq!(
+            permutations(&[1, 1, 2]).collect::<Vec<_>>(),
+            vec![vec![1, 1, 2], vec![1, 2, 1], vec![2, 1, 1]]
+        );
+        assert_eq!(permutations(b"aabbc").count(), 30);
+    }
+
+    #[test]
+    fn test_wraps_around() {
+        let mut a = [3, 2, 1];
+        assert!(!a.next_permutation());
+        assert_eq!(a, [1, 2, 3]);
+        assert!(!a.prev_permutation());
+        assert_eq!(a, [3, 2, 1]);
+    }
+
+    #[test]
+    fn test_prev_is_inverse_of_next() {
+        let mut a = vec![1, 2, 2, 3, 4];
+        loop {
+            let b = a.clone();
+            if !a.next_permutation() {
+                break;
+            }
+            let mut c = a.clone();
+            assert!(c.prev_permutation());
+            assert_eq!(c, b);
+            assert!(b < a);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use cargo_snippet::snippet;
+
+#[snippet("prefix_extremum")]
+fn scan_by<T: Clone>(a: impl Iterator<Item = T>, f: impl Fn(T, T) -> T) -> Vec<T> {
+    let mut res: Vec<T> = vec![];
+    for x in a {
+        let next = match res.last() {
+            Some(acc) => f(acc.clone(), x),
+            None => x,
+        };
+        res.push(next);
+    }
+    res
+}
+
+#[snippet("prefix_extremum")]
+/// `res[i]` is the minimum of `a[..=i]`.
+pub fn prefix_min<T: Ord + Clone>(a: &[T]) -> Vec<T> {
+    scan_by(a.iter().cloned(), std::cmp::min)
+}
+
+#[snippet("prefix_extremum")]
+/// `res[i]` is the maximum of `a[..=i]`.
+pub fn prefix_max<T: Ord + Clone>(a: &[T]) -> Vec<T> {
+    scan_by(a.iter().cloned(), std::cmp::max)
+}
+
+#[snippet("prefix_extremum")]
+/// `res[i]` is the minimum of `a[i..]`.
+pub fn suffix_min<T: Ord + Clone>(a: &[T]) -> Vec<T> {
+    let mut res = scan_by(a.iter().rev().cloned(), std::cmp::min);
+    res.reverse();
+    res
+}
+
+#[snippet("prefix_extremum")]
+/// `res[i]` is the maximum of `a[i..]`.
+pub fn suffix_max<T: Ord + Clone>(a: &[T]) -> Vec<T> {
+    let mut res = scan_by(a.iter().rev().cloned(), std::cmp::max);
+    res.reverse();
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_and_suffix() {
+        let a = [3, 1, 4, 1, 5, 9, 2, 6];
+        assert_eq!(prefix_min(&a), vec![3, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(prefix_max(&a), vec![3, 3, 4, 4, 5, 9, 9, 9]);
+        assert_eq!(suffix_min(&a), vec![1, 1, 1, 1, 2, 2, 2, 6]);
+        assert_eq!(suffix_max(&a), vec![9, 9, 9, 9, 9, 9, 6, 6]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let a: [i32; 0] = [];
+        assert!(prefix_min(&a).is_empty());
+        assert!(suffix_max(&a).is_empty());
+    }
+
+    #[test]
+    fn test_non_copy() {
+        let a = ["b".to_string(), "c".to_string(), "a".to_string()];
+        assert_eq!(prefix_max(&a), vec!["b", "c", "c"]);
+        assert_eq!(suffix_min(&a), vec!["a", "a", "a"]);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let a = (0..100_u64)
+            .map(|i| i * 7_919 % 101 % 30)
+            .collect::<Vec<_>>();
+        let n = a.len();
+        let (pmin, pmax) = (prefix_min(&a), prefix_max(&a));
+        let (smin, smax) = (suffix_min(&a), suffix_max(&a));
+        for i in 0..n {
+            assert_eq!(pmin[i], *a[..=i].iter().min().unwrap());
+            assert_eq!(pmax[i], *a[..=i].iter().max().unwrap());
+            assert_eq!(smin[i], *a[i..].iter().min().unwrap());
+            assert_eq!(smax[i], *a[i..].iter().max().unwrap());
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use crate::algorithms::binary_search::ElementBisect;
+use cargo_snippet::snippet;
+
+#[snippet("prefix_sum", include = "element_bisect")]
+/// Convert `range` within [`0`, `n`) into a half-open pair `(l, r)`.
+fn to_half_open(range: impl std::ops::RangeBounds<usize>, n: usize) -> (usize, usize) {
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+    let l = match range.start_bound() {
+        Included(&l) => l,
+        Excluded(&l) => l + 1,
+        Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Included(&r) => r + 1,
+        Excluded(&r) => r,
+        Unbounded => n,
+    };
+    assert!(
+        l <= r && r <= n,
+        "range {}..{} out of bounds for length {}",
+        l,
+        r,
+        n
+    );
+    (l, r)
+}
+
+#[snippet("prefix_sum", include = "element_bisect")]
+/// Cumulative sums of a slice answering range sums in `O(1)`.
+///
+/// `acc[i]` is the sum of the first `i` elements.
+#[derive(Debug, Clone)]
+pub struct PrefixSum<T> {
+    acc: Vec<T>,
+}
+
+#[snippet("prefix_sum", include = "element_bisect")]
+impl<T> PrefixSum<T>
+where
+    T: Copy + Default + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    pub fn new(a: &[T]) -> Self {
+        let mut acc = Vec::with_capacity(a.len() + 1);
+        acc.push(T::default());
+        for (i, &x) in a.iter().enumerate() {
+            acc.push(acc[i] + x);
+        }
+        Self { acc }
+    }
+
+    /// Sum within `range` such as `2..5`, `..=4` or `..`. Empty range sums up to `T::default()`.
+    pub fn sum(&self, range: impl std::ops::RangeBounds<usize>) -> T {
+        let (l, r) = to_half_open(range, self.acc.len() - 1);
+        self.acc[r] - self.acc[l]
+    }
+
+    /// Smallest `i` such that the sum of the first `i` elements is at least `x`,
+    /// or `n + 1` if there is no such `i`. Elements must be nonnegative.
+    pub fn lower_bound_prefix(&self, x: T) -> usize
+    where
+        T: PartialOrd,
+    {
+        self.acc.bisect_left(&x)
+    }
+}
+
+#[snippet("prefix_sum_2d", include = "prefix_sum")]
+/// Two-dimensional cumulative sums answering rectangle sums in `O(1)`.
+///
+/// `acc[i][j]` is the sum over rows [`0`, `i`) and columns [`0`, `j`).
+#[derive(Debug, Clone)]
+pub struct PrefixSum2D<T> {
+    acc: Vec<Vec<T>>,
+}
+
+#[snippet("prefix_sum_2d", include = "prefix_sum")]
+impl<T> PrefixSum2D<T>
+where
+    T: Copy + Default + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    /// Build from a rectangular table with time-complexity `O(HW)`.
+    pub fn new(a: &[Vec<T>]) -> Self {
+        Self::with_map(a, |&x| x)
+    }
+
+    /// Build from a rectangular table converting each cell by `f`,
+    /// e.g. to accumulate `u32` cells in `i64`.
+    pub fn with_map<U, F: Fn(&U) -> T>(a: &[Vec<U>], f: F) -> Self {
+        let h = a.len();
+        let w = if h == 0 { 0 } else { a[0].len() };
+        let mut acc = vec![vec![T::default(); w + 1]; h + 1];
+        for i in 0..h {
+            assert_eq!(a[i].len(), w);
+            for j in 0..w {
+                acc[i + 1][j + 1] = acc[i][j + 1] + acc[i + 1][j] - acc[i][j] + f(&a[i][j]);
+            }
+        }
+        Self { acc }
+    }
+
+    /// Sum over the rectangle of `rows` and `cols`. Empty rectangle sums up to `T::default()`.
+    pub fn sum(
+        &self,
+        rows: impl std::ops::RangeBounds<usize>,
+        cols: impl std::ops::RangeBounds<usize>,
+    ) -> T {
+        let (i1, i2) = to_half_open(rows, self.acc.len() - 1);
+        let (j1, j2) = to_half_open(cols, self.acc[0].len() - 1);
+        self.acc[i2][j2] + self.acc[i1][j1] - self.acc[i1][j2] - self.acc[i2][j1]
+    }
+}
+
+#[snippet("imos", include = "prefix_sum")]
+/// Range additions recorded as differences, materialized at once by `build`.
+///
+/// `build` consumes `self`, so no addition can follow it.
+/// `T` must be signed since differences go negative, e.g. `Imos<u64>` overflows in `add`.
+#[derive(Debug, Clone)]
+pub struct Imos<T> {
+    diff: Vec<T>,
+}
+
+#[snippet("imos", include = "prefix_sum")]
+impl<T> Imos<T>
+where
+    T: Copy + Default + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    pub fn new(n: usize) -> Self {
+        Self {
+            diff: vec![T::default(); n + 1],
+        }
+    }
+
+    /// Add `v` to every element within `range` with time-complexity `O(1)`.
+    pub fn add(&mut self, range: impl std::ops::RangeBounds<usize>, v: T) {
+        let (l, r) = to_half_open(range, self.diff.len() - 1);
+        self.diff[l] = self.diff[l] + v;
+        self.diff[r] = self.diff[r] - v;
+    }
+
+    /// Resulting array with time-complexity `O(n)`.
+    pub fn build(mut self) -> Vec<T> {
+        self.diff.pop();
+        for i in 1..self.diff.len() {
+            self.diff[i] = self.diff[i - 1] + self.diff[i];
+        }
+        self.diff
+    }
+}
+
+#[snippet("imos", include = "prefix_sum")]
+/// Rectangle additions recorded at four corners, materialized at once by `build`.
+///
+/// `build` consumes `self`, so no addition can follow it.
+/// `T` must be signed as with `Imos`.
+#[derive(Debug, Clone)]
+pub struct Imos2D<T> {
+    diff: Vec<Vec<T>>,
+}
+
+#[snippet("imos", include = "prefix_sum")]
+impl<T> Imos2D<T>
+where
+    T: Copy + Default + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    pub fn new(h: usize, w: usize) -> Self {
+        Self {
+            diff: vec![vec![T::default(); w + 1]; h + 1],
+        }
+    }
+
+    /// Add `v` to every cell in the rectangle of `rows` and `cols` with time-complexity `O(1)`.
+    pub fn add(
+        &mut self,
+        rows: impl std::ops::RangeBounds<usize>,
+        cols: impl std::ops::RangeBounds<usize>,
+        v: T,
+    ) {
+        let (i1, i2) = to_half_open(rows, self.diff.len() - 1);
+        let (j1, j2) = to_half_open(cols, self.diff[0].len() - 1);
+        self.diff[i1][j1] = self.diff[i1][j1] + v;
+        self.diff[i1][j2] = self.diff[i1][j2] - v;
+        self.diff[i2][j1] = self.diff[i2][j1] - v;
+        self.diff[i2][j2] = self.diff[i2][j2] + v;
+    }
+
+    /// Resulting `h` by `w` table with time-complexity `O(HW)`.
+    pub fn build(mut self) -> Vec<Vec<T>> {
+        self.diff.pop();
+        for row in self.diff.iter_mut() {
+            row.pop();
+            for j in 1..row.len() {
+                row[j] = row[j - 1] + row[j];
+            }
+        }
+        for i in 1..self.diff.len() {
+            for j in 0..self.diff[i].len() {
+                self.diff[i][j] = self.diff[i - 1][j] + self.diff[i][j];
+            }
+        }
+        self.diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_array, XorShift};
+
+    #[test]
+    fn test_sum_range_bounds() {
+        let p = PrefixSum::new(&[3_i64, -1, 4, 1, -5, 9]);
+        assert_eq!(p.sum(..), 11);
+        assert_eq!(p.sum(1..4), 4);
+        assert_eq!(p.sum(1..=4), -1);
+        assert_eq!(p.sum(2..), 9);
+        assert_eq!(p.sum(..2), 2);
+        assert_eq!(p.sum(..=0), 3);
+        assert_eq!(p.sum(3..3), 0);
+        assert_eq!(p.sum(6..), 0);
+    }
+
+    #[test]
+    fn test_sum_f64() {
+        let p = PrefixSum::new(&[0.5_f64, 0.25, 0.125]);
+        assert!((p.sum(1..) - 0.375).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sum_matches_naive() {
+        let mut rng = XorShift::new();
+        let a = random_array(&mut rng, 40, 1 << 24)
+            .into_iter()
+            .map(|v| v as i64 - (1 << 23))
+            .collect::<Vec<_>>();
+        let p = PrefixSum::new(&a);
+        for l in 0..=a.len() {
+            for r in l..=a.len() {
+                assert_eq!(p.sum(l..r), a[l..r].iter().sum::<i64>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_lower_bound_prefix() {
+        let p = PrefixSum::new(&[2_u64, 0, 3, 1]);
+        assert_eq!(p.lower_bound_prefix(0), 0);
+        assert_eq!(p.lower_bound_prefix(1), 1);
+        assert_eq!(p.lower_bound_prefix(2), 1);
+        assert_eq!(p.lower_bound_prefix(3), 3);
+        assert_eq!(p.lower_bound_prefix(6), 4);
+        assert_eq!(p.lower_bound_prefix(7), 5);
+    }
+
+    #[test]
+    fn test_sum_2d_matches_brute_force() {
+        let mut rng = XorShift::new();
+        let a = (0..50)
+            .map(|_| (0..50).map(|_| rng.next_u64() as u32).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let p = PrefixSum2D::with_map(&a, |&v| v as i64);
+        for _ in 0..500 {
+            let (i1, i2) = (rng.below(51), rng.below(51));
+            let (j1, j2) = (rng.below(51), rng.below(51));
+            let (i1, i2) = (i1.min(i2), i1.max(i2));
+            let (j1, j2) = (j1.min(j2), j1.max(j2));
+            let expected = a[i1..i2]
+                .iter()
+                .map(|row| row[j1..j2].iter().map(|&v| v as i64).sum::<i64>())
+                .sum::<i64>();
+            assert_eq!(p.sum(i1..i2, j1..j2), expected);
+        }
+        let total = a.iter().flatten().map(|&v| v as i64).sum::<i64>();
+        assert_eq!(p.sum(.., ..), total);
+    }
+
+    #[test]
+    fn test_sum_2d_with_zero_row_and_column() {
+        let a = vec![vec![1, 0, 2], vec![0, 0, 0], vec![3, 0, 4]];
+        let p = PrefixSum2D::new(&a);
+        assert_eq!(p.sum(.., ..), 10);
+        assert_eq!(p.sum(1..2, ..), 0);
+        assert_eq!(p.sum(.., 1..=1), 0);
+        assert_eq!(p.sum(1.., 1..), 4);
+        assert_eq!(p.sum(2..2, ..), 0);
+        assert_eq!(p.sum(.., 3..), 0);
+    }
+
+    #[test]
+    fn test_sum_2d_empty_table() {
+        let a: Vec<Vec<i64>> = vec![];
+        let p = PrefixSum2D::new(&a);
+        assert_eq!(p.sum(.., ..), 0);
+    }
+
+    #[test]
+    fn test_sum_2d_unsigned() {
+        let p = PrefixSum2D::new(&[vec![1_u64, 1], vec![1, 0]]);
+        assert_eq!(p.sum(1..2, 1..2), 0);
+        assert_eq!(p.sum(1.., ..), 1);
+        assert_eq!(p.sum(.., 1..), 1);
+        assert_eq!(p.sum(.., ..), 3);
+    }
+
+    #[test]
+    fn test_imos() {
+        let mut imos = Imos::new(6);
+        imos.add(1..4, 2_i64);
+        imos.add(2..=5, -1);
+        imos.add(..1, 7);
+        imos.add(3..3, 100);
+        imos.add(5.., 10);
+        assert_eq!(imos.build(), vec![7, 2, 1, 1, -1, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn test_imos_unsigned_overflows() {
+        let mut imos = Imos::<u64>::new(5);
+        imos.add(1..3, 5);
+    }
+
+    #[test]
+    fn test_imos_2d() {
+        let mut imos = Imos2D::new(3, 4);
+        imos.add(0..2, 1..3, 1_i64);
+        imos.add(1..3, 2..4, 2);
+        imos.add(2..=2, 0..=0, 5);
+        imos.add(.., 3.., 10);
+        imos.add(1..1, .., 100);
+        assert_eq!(
+            imos.build(),
+            vec![vec![0, 1, 1, 10], vec![0, 1, 3, 12], vec![5, 0, 2, 12]]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn test_imos_2d_unsigned_overflows() {
+        let mut imos = Imos2D::<u64>::new(2, 2);
+        imos.add(0..1, 0..1, 5);
+    }
+
+    #[test]
+    fn test_imos_2d_matches_naive() {
+        let (h, w) = (7, 9);
+        let mut rng = XorShift::new();
+        let mut imos = Imos2D::new(h, w);
+        let mut naive = vec![vec![0_i64; w]; h];
+        for _ in 0..100 {
+            let (i1, i2) = (rng.below(8), rng.below(8));
+            let (j1, j2) = (rng.below(10), rng.below(10));
+            let (i1, i2) = (i1.min(i2), i1.max(i2));
+            let (j1, j2) = (j1.min(j2), j1.max(j2));
+            let v = rng.below(21) as i64 - 10;
+            imos.add(i1..i2, j1..j2, v);
+            for row in naive[i1..i2].iter_mut() {
+                for c in row[j1..j2].iter_mut() {
+                    *c += v;
+                }
+            }
+        }
+        assert_eq!(imos.build(), naive);
+    }
+}
@@ -0,0 +1,176 @@
+use cargo_snippet::snippet;
+
+#[snippet("reroot_dp")]
+/// Tree DP for every choice of the root by rerooting.
+///
+/// DP value of `v` is `leaf_val(v)` with values of its neighbour subtrees folded in:
+/// those below `v` by `up` and the one above `v` by `down`.
+/// Folding must not depend on the order of neighbours.
+///
+/// As neither inverse nor merging of accumulators is required, values excluding
+/// each child are computed by divide and conquer over children,
+/// which takes `O(n log n)` calls of `up` and `down` in total.
+///
+/// # Arguments
+///
+/// * `n` - Number of nodes.
+/// * `adj` - Adjacency list of the tree.
+/// * `leaf_val` - DP value of `v` without any neighbours.
+/// * `up` - Fold DP value of child subtree into accumulator of `v`, as `up(acc, child, v)`.
+/// * `down` - Fold DP value of parent side into accumulator of `v`, as `down(acc, parent, v)`.
+///
+/// # Returns
+///
+/// DP value of the whole tree rooted at each node.
+pub fn reroot_dp<T: Clone>(
+    n: usize,
+    adj: &[Vec<usize>],
+    leaf_val: impl Fn(usize) -> T,
+    up: impl Fn(T, T, usize) -> T,
+    down: impl Fn(T, T, usize) -> T,
+) -> Vec<T> {
+    // Push accumulators with every item but one excluded, in order of `items`.
+    fn exclude_each<T: Clone>(
+        acc: T,
+        items: &[usize],
+        fold: &dyn Fn(T, usize) -> T,
+        out: &mut Vec<T>,
+    ) {
+        if items.len() == 1 {
+            out.push(acc);
+            return;
+        }
+        let (left, right) = items.split_at(items.len() / 2);
+        let with_right = right.iter().fold(acc.clone(), |a, &i| fold(a, i));
+        exclude_each(with_right, left, fold, out);
+        let with_left = left.iter().fold(acc, |a, &i| fold(a, i));
+        exclude_each(with_left, right, fold, out);
+    }
+
+    if n == 0 {
+        return vec![];
+    }
+    let mut parent = vec![!0; n];
+    let mut order = Vec::with_capacity(n);
+    let mut stack = vec![0];
+    parent[0] = 0;
+    while let Some(v) = stack.pop() {
+        order.push(v);
+        for &u in &adj[v] {
+            if parent[u] == !0 {
+                parent[u] = v;
+                stack.push(u);
+            }
+        }
+    }
+    let children = (0..n)
+        .map(|v| {
+            adj[v]
+                .iter()
+                .cloned()
+                .filter(|&u| parent[u] == v)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut sub: Vec<Option<T>> = vec![None; n];
+    for &v in order.iter().rev() {
+        let acc = children[v]
+            .iter()
+            .fold(leaf_val(v), |a, &c| up(a, sub[c].clone().unwrap(), v));
+        sub[v] = Some(acc);
+    }
+
+    let mut above: Vec<Option<T>> = vec![None; n];
+    let mut res: Vec<Option<T>> = vec![None; n];
+    let mut excluded = vec![];
+    for &v in &order {
+        let start = match above[v].take() {
+            Some(x) => down(leaf_val(v), x, v),
+            None => leaf_val(v),
+        };
+        let fold = |a: T, c: usize| up(a, sub[c].clone().unwrap(), v);
+        res[v] = Some(children[v].iter().fold(start.clone(), |a, &c| fold(a, c)));
+        if children[v].is_empty() {
+            continue;
+        }
+        excluded.clear();
+        exclude_each(start, &children[v], &fold, &mut excluded);
+        for (&c, x) in children[v].iter().zip(excluded.drain(..)) {
+            above[c] = Some(x);
+        }
+    }
+    res.into_iter().map(|x| x.unwrap()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_tree, to_adjacency, XorShift};
+
+    fn random_adjacency(n: usize, seed: u64) -> Vec<Vec<usize>> {
+        to_adjacency(n, &random_tree(&mut XorShift::with_seed(seed), n))
+    }
+
+    fn distances(adj: &[Vec<usize>], s: usize) -> Vec<usize> {
+        let mut dist = vec![!0; adj.len()];
+        let mut queue = std::collections::VecDeque::new();
+        dist[s] = 0;
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            for &u in &adj[v] {
+                if dist[u] == !0 {
+                    dist[u] = dist[v] + 1;
+                    queue.push_back(u);
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn test_sum_of_distances() {
+        for seed in 0..5 {
+            let n = 60;
+            let adj = random_adjacency(n, seed);
+            // (Number of nodes, sum of distances from the root) of a subtree.
+            let merge =
+                |acc: (usize, usize), x: (usize, usize), _| (acc.0 + x.0, acc.1 + x.1 + x.0);
+            let res = reroot_dp(n, &adj, |_| (1, 0), merge, merge);
+            for (v, &(size, sum)) in res.iter().enumerate() {
+                assert_eq!(size, n);
+                assert_eq!(sum, distances(&adj, v).iter().sum::<usize>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_eccentricity_and_diameter() {
+        let n = 60;
+        let adj = random_adjacency(n, 42);
+        let merge = |acc: usize, x: usize, _| acc.max(x + 1);
+        let res = reroot_dp(n, &adj, |_| 0, merge, merge);
+        for (v, &ecc) in res.iter().enumerate() {
+            assert_eq!(ecc, *distances(&adj, v).iter().max().unwrap());
+        }
+        let diameter = (0..n)
+            .map(|v| *distances(&adj, v).iter().max().unwrap())
+            .max()
+            .unwrap();
+        assert_eq!(*res.iter().max().unwrap(), diameter);
+    }
+
+    #[test]
+    fn test_all_equal() {
+        let n = 10;
+        let adj = random_adjacency(n, 7);
+        let res = reroot_dp(n, &adj, |_| 3, |acc, _, _| acc, |acc, _, _| acc);
+        assert_eq!(res, vec![3; n]);
+    }
+
+    #[test]
+    fn test_single_node() {
+        let res = reroot_dp(1, &[vec![]], |v| v + 5, |a, b, _| a + b, |a, b, _| a + b);
+        assert_eq!(res, vec![5]);
+    }
+}
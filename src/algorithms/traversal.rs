@@ -0,0 +1,129 @@
+use cargo_snippet::snippet;
+
+#[snippet("traversal")]
+/// Depth-first pre-order of vertices reachable from `root` and their parents in the DFS tree,
+/// with time-complexity `O(n + m)`.
+///
+/// Neighbors are visited in the order of `adj[v]` as a recursive DFS would,
+/// but an explicit stack is used so that deep graphs do not overflow the call stack.
+pub fn dfs_order(n: usize, adj: &[Vec<usize>], root: usize) -> (Vec<usize>, Vec<Option<usize>>) {
+    assert!(adj.len() == n && root < n);
+    let mut order = Vec::with_capacity(n);
+    let mut parent = vec![None; n];
+    let mut visited = vec![false; n];
+    let mut stack = vec![(root, None)];
+    while let Some((v, p)) = stack.pop() {
+        if visited[v] {
+            continue;
+        }
+        visited[v] = true;
+        parent[v] = p;
+        order.push(v);
+        for &u in adj[v].iter().rev() {
+            if !visited[u] {
+                stack.push((u, Some(v)));
+            }
+        }
+    }
+    (order, parent)
+}
+
+#[snippet("traversal")]
+/// Breadth-first order of vertices reachable from `root` and their parents in the BFS tree,
+/// with time-complexity `O(n + m)`.
+pub fn bfs_order(n: usize, adj: &[Vec<usize>], root: usize) -> (Vec<usize>, Vec<Option<usize>>) {
+    assert!(adj.len() == n && root < n);
+    let mut order = Vec::with_capacity(n);
+    let mut parent = vec![None; n];
+    let mut visited = vec![false; n];
+    let mut queue = std::collections::VecDeque::new();
+    visited[root] = true;
+    queue.push_back(root);
+    while let Some(v) = queue.pop_front() {
+        order.push(v);
+        for &u in &adj[v] {
+            if !visited[u] {
+                visited[u] = true;
+                parent[u] = Some(v);
+                queue.push_back(u);
+            }
+        }
+    }
+    (order, parent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::to_adjacency;
+
+    fn dfs_recursive(v: usize, adj: &[Vec<usize>], visited: &mut [bool], order: &mut Vec<usize>) {
+        visited[v] = true;
+        order.push(v);
+        for &u in &adj[v] {
+            if !visited[u] {
+                dfs_recursive(u, adj, visited, order);
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_tree() {
+        //     0
+        //    / \
+        //   1   2
+        //  / \   \
+        // 3   4   5
+        let adj = to_adjacency(6, &[(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)]);
+        let (order, parent) = dfs_order(6, &adj, 0);
+        assert_eq!(order, vec![0, 1, 3, 4, 2, 5]);
+        assert_eq!(
+            parent,
+            vec![None, Some(0), Some(0), Some(1), Some(1), Some(2)]
+        );
+        let (order, parent) = bfs_order(6, &adj, 0);
+        assert_eq!(order, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            parent,
+            vec![None, Some(0), Some(0), Some(1), Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn test_graph_with_cycles_matches_recursive_dfs() {
+        let edges = [
+            (0, 3),
+            (3, 1),
+            (1, 0),
+            (2, 4),
+            (3, 4),
+            (4, 5),
+            (5, 2),
+            (0, 5),
+        ];
+        let adj = to_adjacency(7, &edges);
+        let mut visited = vec![false; 7];
+        let mut expected = vec![];
+        dfs_recursive(0, &adj, &mut visited, &mut expected);
+        let (order, parent) = dfs_order(7, &adj, 0);
+        assert_eq!(order, expected);
+        assert_eq!(parent[6], None);
+        assert!(!order.contains(&6));
+        let (order, _) = bfs_order(7, &adj, 0);
+        assert_eq!(order, vec![0, 3, 1, 5, 4, 2]);
+    }
+
+    #[test]
+    fn test_deep_path() {
+        let n = 100_000;
+        let edges = (1..n).map(|i| (i - 1, i)).collect::<Vec<_>>();
+        let adj = to_adjacency(n, &edges);
+        let expected_parent = (0..n).map(|i| i.checked_sub(1)).collect::<Vec<_>>();
+        let (order, parent) = dfs_order(n, &adj, 0);
+        assert_eq!(order, (0..n).collect::<Vec<_>>());
+        assert_eq!(parent, expected_parent);
+        let (order, parent) = bfs_order(n, &adj, 0);
+        assert_eq!(order, (0..n).collect::<Vec<_>>());
+        assert_eq!(parent, expected_parent);
+    }
+}
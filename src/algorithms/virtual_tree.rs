@@ -0,0 +1,96 @@
+use crate::data_structure::hld::Hld;
+use cargo_snippet::snippet;
+
+#[snippet("virtual_tree", include = "hld")]
+/// Edges `(parent, child)` of the virtual tree (auxiliary tree) of `query_nodes`
+/// with time-complexity `O(k log n)` where `k = query_nodes.len()`.
+///
+/// The virtual tree consists of `query_nodes` and LCAs of every pair of them,
+/// which are at most `2k - 1` nodes, and `parent` is the nearest ancestor among them.
+/// `hld` is used for both the DFS order and LCAs of the original tree.
+pub fn virtual_tree(query_nodes: &[usize], hld: &Hld) -> Vec<(usize, usize)> {
+    let mut nodes = query_nodes.to_vec();
+    nodes.sort_by_key(|&v| hld.pos(v));
+    nodes.dedup();
+    // LCAs of adjacent nodes in DFS order cover those of every pair.
+    for i in 1..nodes.len() {
+        let w = hld.lca(nodes[i - 1], nodes[i]);
+        nodes.push(w);
+    }
+    nodes.sort_by_key(|&v| hld.pos(v));
+    nodes.dedup();
+    (1..nodes.len())
+        .map(|i| (hld.lca(nodes[i - 1], nodes[i]), nodes[i]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    //           0
+    //         /   \
+    //        1     2
+    //       / \     \
+    //      3   4     5
+    //     /   / \     \
+    //    6   7   8     9
+    const EDGES: [(usize, usize); 9] = [
+        (0, 1),
+        (0, 2),
+        (1, 3),
+        (1, 4),
+        (2, 5),
+        (3, 6),
+        (4, 7),
+        (4, 8),
+        (5, 9),
+    ];
+
+    fn check(query_nodes: &[usize], hld: &Hld) -> Vec<(usize, usize)> {
+        let mut edges = virtual_tree(query_nodes, hld);
+        let mut expected = query_nodes.iter().cloned().collect::<BTreeSet<_>>();
+        for &u in query_nodes {
+            for &v in query_nodes {
+                expected.insert(hld.lca(u, v));
+            }
+        }
+        let mut nodes = BTreeSet::new();
+        for &(p, c) in &edges {
+            assert_eq!(hld.lca(p, c), p);
+            assert_ne!(p, c);
+            nodes.insert(p);
+            nodes.insert(c);
+        }
+        if query_nodes.len() == 1 {
+            nodes.insert(query_nodes[0]);
+        }
+        assert_eq!(nodes, expected);
+        // Every node but the root has exactly one parent.
+        assert_eq!(edges.len() + 1, nodes.len());
+        let children = edges.iter().map(|&(_, c)| c).collect::<BTreeSet<_>>();
+        assert_eq!(children.len(), edges.len());
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn test_virtual_tree() {
+        let hld = Hld::new(10, &EDGES, 0);
+        assert_eq!(
+            check(&[6, 7, 8], &hld),
+            vec![(1, 4), (1, 6), (4, 7), (4, 8)]
+        );
+        assert_eq!(check(&[9, 6], &hld), vec![(0, 6), (0, 9)]);
+        assert_eq!(check(&[1, 8, 3], &hld), vec![(1, 3), (1, 8)]);
+        check(&[7, 7, 0, 9, 2], &hld);
+    }
+
+    #[test]
+    fn test_single_node() {
+        let hld = Hld::new(10, &EDGES, 0);
+        assert_eq!(check(&[5], &hld), vec![]);
+        assert_eq!(virtual_tree(&[], &hld), vec![]);
+    }
+}
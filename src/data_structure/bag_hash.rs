@@ -0,0 +1,80 @@
+use cargo_snippet::snippet;
+
+#[snippet("bag_hash")]
+/// Order-independent hash of a multiset of `u64`.
+///
+/// The hash is the wrapping sum of a keyed SplitMix64 of each element,
+/// so that multiplicities are distinguished unlike XOR.
+/// Two multisets have the same hash with high probability iff they are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BagHash {
+    seed: u64,
+    hash: u64,
+}
+
+#[snippet("bag_hash")]
+impl BagHash {
+    /// Constructs a hash of the empty multiset. Use a random `seed` against anti-hash tests.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: Self::mix(seed),
+            hash: 0,
+        }
+    }
+
+    fn mix(x: u64) -> u64 {
+        let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn insert(&mut self, x: u64) {
+        self.hash = self.hash.wrapping_add(Self::mix(x ^ self.seed));
+    }
+
+    /// Removes `x`, which is expected to be in the multiset.
+    pub fn remove(&mut self, x: u64) {
+        self.hash = self.hash.wrapping_sub(Self::mix(x ^ self.seed));
+    }
+
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bag(seed: u64, xs: &[u64]) -> BagHash {
+        let mut h = BagHash::new(seed);
+        for &x in xs {
+            h.insert(x);
+        }
+        h
+    }
+
+    #[test]
+    fn test_order_independent() {
+        assert_eq!(bag(42, &[1, 2, 3]).hash(), bag(42, &[3, 1, 2]).hash());
+    }
+
+    #[test]
+    fn test_multiplicity() {
+        assert_ne!(bag(42, &[1, 1, 2]).hash(), bag(42, &[1, 2, 2]).hash());
+        assert_ne!(bag(42, &[1, 1]).hash(), bag(42, &[]).hash());
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut h = bag(7, &[5, 8, 8]);
+        let before = h.hash();
+        h.insert(13);
+        assert_ne!(h.hash(), before);
+        h.remove(13);
+        assert_eq!(h.hash(), before);
+        h.remove(8);
+        assert_eq!(h.hash(), bag(7, &[8, 5]).hash());
+    }
+}
@@ -1,17 +1,59 @@
 use cargo_snippet::snippet;
 
 #[snippet("dsu")]
-pub struct DisjointSet {
+/// Signed integer type storing a parent index or a negated size in `DisjointSet`.
+pub trait DsuIndex: Copy {
+    fn from_isize(x: isize) -> Self;
+    fn to_isize(self) -> isize;
+}
+
+#[snippet("dsu")]
+impl DsuIndex for i32 {
+    fn from_isize(x: isize) -> Self {
+        x as i32
+    }
+    fn to_isize(self) -> isize {
+        self as isize
+    }
+}
+
+#[snippet("dsu")]
+impl DsuIndex for i64 {
+    fn from_isize(x: isize) -> Self {
+        x as i64
+    }
+    fn to_isize(self) -> isize {
+        self as isize
+    }
+}
+
+#[snippet("dsu")]
+/// Disjoint set union whose nodes take `size_of::<I>()` bytes each.
+pub struct DisjointSet<I = i32> {
     n: usize,
-    parent_or_size: Vec<isize>,
+    parent_or_size: Vec<I>,
 }
 
 #[snippet("dsu")]
 impl DisjointSet {
     pub fn new(size: usize) -> Self {
+        Self::with_index_type(size)
+    }
+}
+
+#[snippet("dsu")]
+impl<I: DsuIndex> DisjointSet<I> {
+    /// Constructs a new DisjointSet backed by `I`, e.g. `DisjointSet::<i64>::with_index_type(n)`.
+    pub fn with_index_type(size: usize) -> Self {
+        let neg = -(size as isize);
+        assert_eq!(
+            I::from_isize(neg).to_isize(),
+            neg,
+            "DisjointSet: too many nodes"
+        );
         Self {
             n: size,
-            parent_or_size: vec![-1; size],
+            parent_or_size: vec![I::from_isize(-1); size],
         }
     }
 
@@ -23,11 +65,12 @@ impl DisjointSet {
         if x == y {
             return x;
         }
-        if -self.parent_or_size[x] < -self.parent_or_size[y] {
+        if self.parent_or_size[x].to_isize() > self.parent_or_size[y].to_isize() {
             std::mem::swap(&mut x, &mut y);
         }
-        self.parent_or_size[x] += self.parent_or_size[y];
-        self.parent_or_size[y] = x as isize;
+        let size = self.parent_or_size[x].to_isize() + self.parent_or_size[y].to_isize();
+        self.parent_or_size[x] = I::from_isize(size);
+        self.parent_or_size[y] = I::from_isize(x as isize);
         x
     }
 
@@ -39,23 +82,32 @@ impl DisjointSet {
 
     pub fn leader(&mut self, a: usize) -> usize {
         assert!(a < self.n);
-        if self.parent_or_size[a] < 0 {
+        let p = self.parent_or_size[a].to_isize();
+        if p < 0 {
             a
         } else {
-            self.parent_or_size[a] = self.leader(self.parent_or_size[a] as usize) as isize;
-            self.parent_or_size[a] as usize
+            let x = self.leader(p as usize);
+            self.parent_or_size[a] = I::from_isize(x as isize);
+            x
         }
     }
 
     pub fn size(&mut self, a: usize) -> usize {
         let x = self.leader(a);
-        -self.parent_or_size[x] as usize
+        -self.parent_or_size[x].to_isize() as usize
+    }
+
+    /// Sorted members of the set containing `a` with time-complexity `O(n α(n))`.
+    pub fn members_of(&mut self, a: usize) -> Vec<usize> {
+        let x = self.leader(a);
+        (0..self.n).filter(|&i| self.leader(i) == x).collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::XorShift;
 
     #[test]
     #[should_panic]
@@ -95,4 +147,45 @@ mod tests {
         dsu.merge(1, 5);
         assert_eq!(dsu.size(3), 3);
     }
+
+    #[test]
+    fn test_members_of() {
+        let mut dsu = DisjointSet::new(8);
+        dsu.merge(6, 1);
+        dsu.merge(3, 6);
+        dsu.merge(2, 5);
+        assert_eq!(dsu.members_of(3), vec![1, 3, 6]);
+        assert_eq!(dsu.members_of(5), vec![2, 5]);
+        assert_eq!(dsu.members_of(3).len(), dsu.size(3));
+        dsu.merge(1, 3);
+        assert!(dsu.same(0, 0));
+        assert_eq!(dsu.members_of(1), vec![1, 3, 6]);
+    }
+
+    #[test]
+    fn test_members_of_singleton() {
+        let mut dsu = DisjointSet::new(4);
+        dsu.merge(0, 1);
+        assert_eq!(dsu.members_of(2), vec![2]);
+        assert_eq!(dsu.members_of(2).len(), dsu.size(2));
+    }
+
+    #[test]
+    fn test_i32_backed_agrees_with_i64_backed() {
+        let n = 3_000_000;
+        let mut small = DisjointSet::new(n);
+        let mut large = DisjointSet::<i64>::with_index_type(n);
+        let mut rng = XorShift::new();
+        let mut next = || rng.below(n);
+        for _ in 0..n {
+            let (a, b) = (next(), next());
+            assert_eq!(small.merge(a, b), large.merge(a, b));
+        }
+        for _ in 0..10_000 {
+            let (a, b) = (next(), next());
+            assert_eq!(small.same(a, b), large.same(a, b));
+            assert_eq!(small.size(a), large.size(a));
+        }
+        assert_eq!(std::mem::size_of_val(&small.parent_or_size[0]), 4);
+    }
 }
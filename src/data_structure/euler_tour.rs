@@ -0,0 +1,80 @@
+use cargo_snippet::snippet;
+
+#[snippet("euler_tour")]
+/// Euler tour of a tree with `n` vertices and `edges` rooted at `root`.
+///
+/// Returns `(tin, tout)` where vertices are numbered in preorder by `tin`,
+/// so that the subtree of `u` corresponds to [`tin[u]`, `tout[u]`).
+pub fn euler_tour(n: usize, edges: &[(usize, usize)], root: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut adj = vec![vec![]; n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    let mut tin = vec![!0; n];
+    let mut tout = vec![0; n];
+    let mut cnt = 0;
+    // (vertex, index of next neighbour to visit)
+    let mut stack = vec![(root, 0)];
+    tin[root] = cnt;
+    cnt += 1;
+    while let Some(&mut (v, ref mut i)) = stack.last_mut() {
+        if *i < adj[v].len() {
+            let u = adj[v][*i];
+            *i += 1;
+            if tin[u] == !0 {
+                tin[u] = cnt;
+                cnt += 1;
+                stack.push((u, 0));
+            }
+        } else {
+            tout[v] = cnt;
+            stack.pop();
+        }
+    }
+    (tin, tout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subtree_ranges() {
+        //       0
+        //     / | \
+        //    1  2  3
+        //   / \    |
+        //  4   5   6
+        //          |
+        //          7
+        let edges = [(0, 1), (0, 2), (0, 3), (1, 4), (1, 5), (3, 6), (6, 7)];
+        let (tin, tout) = euler_tour(8, &edges, 0);
+        let descendants = [
+            vec![0, 1, 2, 3, 4, 5, 6, 7],
+            vec![1, 4, 5],
+            vec![2],
+            vec![3, 6, 7],
+            vec![4],
+            vec![5],
+            vec![6, 7],
+            vec![7],
+        ];
+        for (u, desc) in descendants.iter().enumerate() {
+            let inside = (0..8)
+                .filter(|&v| tin[u] <= tin[v] && tin[v] < tout[u])
+                .collect::<Vec<_>>();
+            assert_eq!(inside, *desc);
+        }
+    }
+
+    #[test]
+    fn test_tin_is_permutation() {
+        let edges = [(2, 0), (2, 1), (1, 3)];
+        let (mut tin, tout) = euler_tour(4, &edges, 2);
+        assert_eq!(tin[2], 0);
+        assert_eq!(tout[2], 4);
+        tin.sort_unstable();
+        assert_eq!(tin, vec![0, 1, 2, 3]);
+    }
+}
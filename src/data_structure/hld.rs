@@ -0,0 +1,218 @@
+use cargo_snippet::snippet;
+
+#[snippet("hld")]
+/// Heavy-light decomposition of a tree.
+///
+/// Vertices are laid out on a position array so that every heavy path
+/// and every subtree occupy contiguous ranges,
+/// which can be fed to a segment tree for path and subtree queries.
+pub struct Hld {
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    head: Vec<usize>,
+    pos: Vec<usize>,
+}
+
+#[snippet("hld")]
+impl Hld {
+    /// Constructs a new decomposition of a tree with `n` vertices and `edges` rooted at `root`.
+    pub fn new(n: usize, edges: &[(usize, usize)], root: usize) -> Self {
+        assert!(root < n && edges.len() + 1 == n);
+        let mut adj = vec![vec![]; n];
+        for &(u, v) in edges {
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+
+        let mut parent = vec![!0; n];
+        let mut depth = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        parent[root] = root;
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            for &u in &adj[v] {
+                if parent[u] == !0 {
+                    parent[u] = v;
+                    depth[u] = depth[v] + 1;
+                    stack.push(u);
+                }
+            }
+        }
+
+        let mut size = vec![1; n];
+        let mut heavy: Vec<Option<usize>> = vec![None; n];
+        for &v in order.iter().rev() {
+            if v == root {
+                continue;
+            }
+            let p = parent[v];
+            size[p] += size[v];
+            let is_heavier = match heavy[p] {
+                Some(h) => size[h] < size[v],
+                None => true,
+            };
+            if is_heavier {
+                heavy[p] = Some(v);
+            }
+        }
+
+        // Walk down each heavy path, deferring light children onto the stack.
+        let mut head = vec![root; n];
+        let mut pos = vec![0; n];
+        let mut cur = 0;
+        let mut stack = vec![root];
+        while let Some(h) = stack.pop() {
+            let mut v = h;
+            loop {
+                pos[v] = cur;
+                cur += 1;
+                for &u in &adj[v] {
+                    if u != parent[v] && Some(u) != heavy[v] {
+                        head[u] = u;
+                        stack.push(u);
+                    }
+                }
+                match heavy[v] {
+                    Some(u) => {
+                        head[u] = h;
+                        v = u;
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        Self {
+            parent,
+            depth,
+            size,
+            head,
+            pos,
+        }
+    }
+
+    /// Position of vertex `v` on the position array.
+    pub fn pos(&self, v: usize) -> usize {
+        self.pos[v]
+    }
+
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = (u, v);
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] < self.depth[self.head[v]] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            u = self.parent[self.head[u]];
+        }
+        if self.depth[u] < self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    /// Disjoint ranges [`l`, `r`) on the position array which together cover
+    /// exactly the vertices on the path between `u` and `v`.
+    pub fn path(&self, u: usize, v: usize) -> impl Iterator<Item = (usize, usize)> {
+        let mut segments = vec![];
+        let (mut u, mut v) = (u, v);
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] < self.depth[self.head[v]] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            segments.push((self.pos[self.head[u]], self.pos[u] + 1));
+            u = self.parent[self.head[u]];
+        }
+        let (l, r) = (self.pos[u].min(self.pos[v]), self.pos[u].max(self.pos[v]));
+        segments.push((l, r + 1));
+        segments.into_iter()
+    }
+
+    /// Range [`l`, `r`) on the position array covering the subtree of `u`.
+    pub fn subtree(&self, u: usize) -> (usize, usize) {
+        (self.pos[u], self.pos[u] + self.size[u])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_tree, to_adjacency, XorShift};
+
+    fn tree_path(n: usize, edges: &[(usize, usize)], u: usize, v: usize) -> Vec<usize> {
+        let adj = to_adjacency(n, edges);
+        let mut prev = vec![!0; n];
+        let mut stack = vec![u];
+        prev[u] = u;
+        while let Some(w) = stack.pop() {
+            for &x in &adj[w] {
+                if prev[x] == !0 {
+                    prev[x] = w;
+                    stack.push(x);
+                }
+            }
+        }
+        let mut res = vec![v];
+        let mut w = v;
+        while w != u {
+            w = prev[w];
+            res.push(w);
+        }
+        res
+    }
+
+    #[test]
+    fn test_path_covers_exactly_tree_path() {
+        for seed in 0..5 {
+            let n = 50;
+            let edges = random_tree(&mut XorShift::with_seed(seed), n);
+            let hld = Hld::new(n, &edges, (seed as usize * 7) % n);
+            for u in 0..n {
+                for v in (0..n).step_by(7) {
+                    let mut covered = hld.path(u, v).flat_map(|(l, r)| l..r).collect::<Vec<_>>();
+                    covered.sort_unstable();
+                    let mut expected = tree_path(n, &edges, u, v)
+                        .iter()
+                        .map(|&w| hld.pos(w))
+                        .collect::<Vec<_>>();
+                    expected.sort_unstable();
+                    assert_eq!(covered, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_subtree() {
+        //     0
+        //    / \
+        //   1   2
+        //  / \   \
+        // 3   4   5
+        let edges = [(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)];
+        let hld = Hld::new(6, &edges, 0);
+        for (v, members) in &[
+            (0, vec![0, 1, 2, 3, 4, 5]),
+            (1, vec![1, 3, 4]),
+            (2, vec![2, 5]),
+            (3, vec![3]),
+        ] {
+            let (l, r) = hld.subtree(*v);
+            let mut positions = members.iter().map(|&w| hld.pos(w)).collect::<Vec<_>>();
+            positions.sort_unstable();
+            assert_eq!(positions, (l..r).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_lca() {
+        let edges = [(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)];
+        let hld = Hld::new(6, &edges, 0);
+        assert_eq!(hld.lca(3, 4), 1);
+        assert_eq!(hld.lca(3, 5), 0);
+        assert_eq!(hld.lca(1, 4), 1);
+        assert_eq!(hld.lca(5, 5), 5);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::data_structure::dsu::DisjointSet;
+use cargo_snippet::snippet;
+
+#[snippet("kruskal_tree", include = "dsu")]
+/// Kruskal reconstruction tree.
+///
+/// Edges are merged in increasing order of weight, and every merge of two components
+/// creates a new internal node whose children are the roots of the merged components.
+/// Original vertices `0..n` are leaves and internal nodes are numbered from `n`
+/// in the order of creation.
+///
+/// * `parent`: `parent[i]` is the parent of node `i`, or `i` itself if `i` is a root.
+/// * `children`: Children of each node. Empty for leaves.
+/// * `weight`: `weight[i - n]` is the weight of the edge which created internal node `i`.
+pub struct KruskalTree<T> {
+    n: usize,
+    pub parent: Vec<usize>,
+    pub children: Vec<Vec<usize>>,
+    pub weight: Vec<T>,
+    depth: Vec<usize>,
+    ancestor: Vec<Vec<usize>>,
+}
+
+#[snippet("kruskal_tree", include = "dsu")]
+impl<T: Copy + Ord> KruskalTree<T> {
+    /// Constructs a new tree from `n` vertices and undirected `edges` of `(u, v, weight)`.
+    pub fn new(n: usize, edges: &[(usize, usize, T)]) -> Self {
+        let mut edges = edges.to_vec();
+        edges.sort_by_key(|&(_, _, w)| w);
+
+        let mut dsu = DisjointSet::new(n);
+        let mut node_of = (0..n).collect::<Vec<_>>();
+        let mut parent = (0..n).collect::<Vec<_>>();
+        let mut children = vec![vec![]; n];
+        let mut weight = vec![];
+        for &(u, v, w) in &edges {
+            let (a, b) = (dsu.leader(u), dsu.leader(v));
+            if a == b {
+                continue;
+            }
+            let id = parent.len();
+            parent[node_of[a]] = id;
+            parent[node_of[b]] = id;
+            parent.push(id);
+            children.push(vec![node_of[a], node_of[b]]);
+            weight.push(w);
+            node_of[dsu.merge(a, b)] = id;
+        }
+
+        // Parents always have larger indices than their children.
+        let m = parent.len();
+        let mut depth = vec![0; m];
+        for i in (0..m).rev() {
+            if parent[i] != i {
+                depth[i] = depth[parent[i]] + 1;
+            }
+        }
+        let mut ancestor = vec![parent.clone()];
+        while 1 << ancestor.len() < m {
+            let prev = ancestor.last().unwrap();
+            let next = (0..m).map(|i| prev[prev[i]]).collect::<Vec<_>>();
+            ancestor.push(next);
+        }
+
+        Self {
+            n,
+            parent,
+            children,
+            weight,
+            depth,
+            ancestor,
+        }
+    }
+
+    /// Number of nodes including both leaves and internal nodes.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Lowest common ancestor of `u` and `v`. `None` if they are not connected.
+    pub fn lca(&self, u: usize, v: usize) -> Option<usize> {
+        assert!(u < self.len() && v < self.len());
+        let (mut u, mut v) = if self.depth[u] < self.depth[v] {
+            (v, u)
+        } else {
+            (u, v)
+        };
+        let diff = self.depth[u] - self.depth[v];
+        for (k, anc) in self.ancestor.iter().enumerate() {
+            if diff >> k & 1 == 1 {
+                u = anc[u];
+            }
+        }
+        if u == v {
+            return Some(u);
+        }
+        for anc in self.ancestor.iter().rev() {
+            if anc[u] != anc[v] {
+                u = anc[u];
+                v = anc[v];
+            }
+        }
+        if self.parent[u] == self.parent[v] && self.parent[u] != u {
+            Some(self.parent[u])
+        } else {
+            None
+        }
+    }
+
+    /// Minimum possible maximum edge weight on a path between vertices `u` and `v`.
+    /// `None` if `u == v` or they are not connected.
+    pub fn max_edge_on_path(&self, u: usize, v: usize) -> Option<T> {
+        assert!(u < self.n && v < self.n);
+        if u == v {
+            return None;
+        }
+        self.lca(u, v).map(|x| self.weight[x - self.n])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> KruskalTree<i64> {
+        // 0 -5- 1 -2- 2
+        // |    /      |
+        // 1   7       4
+        // |  /        |
+        // 3 -- 9 ---- 4    5 (isolated)
+        let edges = [
+            (0, 1, 5),
+            (1, 2, 2),
+            (0, 3, 1),
+            (1, 3, 7),
+            (2, 4, 4),
+            (3, 4, 9),
+        ];
+        KruskalTree::new(6, &edges)
+    }
+
+    #[test]
+    fn test_tree_structure() {
+        let t = sample();
+        assert_eq!(t.len(), 6 + 4);
+        assert_eq!(t.weight, vec![1, 2, 4, 5]);
+        assert_eq!(t.children[6], vec![0, 3]);
+        assert_eq!(t.parent[9], 9);
+        assert_eq!(t.parent[5], 5);
+        assert!(t.children[5].is_empty());
+    }
+
+    #[test]
+    fn test_max_edge_on_path() {
+        let t = sample();
+        assert_eq!(t.max_edge_on_path(0, 3), Some(1));
+        assert_eq!(t.max_edge_on_path(1, 2), Some(2));
+        assert_eq!(t.max_edge_on_path(1, 4), Some(4));
+        assert_eq!(t.max_edge_on_path(3, 1), Some(5));
+        assert_eq!(t.max_edge_on_path(3, 4), Some(5));
+        assert_eq!(t.max_edge_on_path(2, 2), None);
+        assert_eq!(t.max_edge_on_path(0, 5), None);
+    }
+
+    #[test]
+    fn test_lca() {
+        let t = sample();
+        assert_eq!(t.lca(0, 0), Some(0));
+        assert_eq!(t.lca(0, 6), Some(6));
+        assert_eq!(t.lca(0, 2), Some(9));
+        assert_eq!(t.lca(5, 9), None);
+    }
+}
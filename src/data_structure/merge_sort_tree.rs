@@ -0,0 +1,132 @@
+use crate::algorithms::binary_search::ElementBisect;
+use cargo_snippet::snippet;
+
+#[snippet("merge_sort_tree", include = "element_bisect")]
+/// Segment tree whose nodes hold sorted values of their ranges,
+/// for static order statistics within ranges.
+#[derive(Debug, Clone)]
+pub struct MergeSortTree {
+    len: usize,
+    n: usize,
+    node: Vec<Vec<i64>>,
+}
+
+#[snippet("merge_sort_tree", include = "element_bisect")]
+impl MergeSortTree {
+    /// Constructs the tree with time-complexity `O(n log n)`.
+    pub fn new(a: &[i64]) -> Self {
+        let n = a.len().next_power_of_two();
+        let mut node = vec![vec![]; n << 1];
+        for (i, &x) in a.iter().enumerate() {
+            node[i + n] = vec![x];
+        }
+        for i in (1..n).rev() {
+            let (l, r) = (&node[i << 1], &node[i << 1 | 1]);
+            let mut merged = Vec::with_capacity(l.len() + r.len());
+            let (mut p, mut q) = (0, 0);
+            while p < l.len() || q < r.len() {
+                if q == r.len() || (p < l.len() && l[p] <= r[q]) {
+                    merged.push(l[p]);
+                    p += 1;
+                } else {
+                    merged.push(r[q]);
+                    q += 1;
+                }
+            }
+            node[i] = merged;
+        }
+        Self {
+            len: a.len(),
+            n,
+            node,
+        }
+    }
+
+    /// Number of values at most `x` within range [`left`, `right`)
+    /// with time-complexity `O(log^2 n)`.
+    pub fn count_le(&self, left: usize, right: usize, x: i64) -> usize {
+        assert!(left <= right && right <= self.len);
+        let mut l = left + self.n;
+        let mut r = right + self.n;
+        let mut res = 0;
+        while l < r {
+            if l & 1 == 1 {
+                res += self.node[l].bisect_right(&x);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                res += self.node[r].bisect_right(&x);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        res
+    }
+
+    /// `k`-th (0-indexed) smallest value within range [`left`, `right`)
+    /// with time-complexity `O(log^3 n)`.
+    pub fn kth_smallest(&self, left: usize, right: usize, k: usize) -> i64 {
+        assert!(left <= right && k < right - left);
+        // Smallest value among all whose count within the range exceeds `k`.
+        let all = &self.node[1];
+        let (mut lo, mut hi) = (0, all.len() - 1);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.count_le(left, right, all[mid]) > k {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        all[lo]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test_small() {
+        let t = MergeSortTree::new(&[5, 1, 4, 1, 3, 9, 2]);
+        assert_eq!(t.count_le(0, 7, 1), 2);
+        assert_eq!(t.count_le(1, 5, 3), 3);
+        assert_eq!(t.count_le(2, 2, 100), 0);
+        assert_eq!(t.kth_smallest(0, 7, 0), 1);
+        assert_eq!(t.kth_smallest(0, 7, 6), 9);
+        assert_eq!(t.kth_smallest(2, 6, 1), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_count_le_beyond_len() {
+        let t = MergeSortTree::new(&[5, 1, 4]);
+        t.count_le(0, 4, 10);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = XorShift::new();
+        for len in 1..40 {
+            let a = (0..len)
+                .map(|_| rng.below(21) as i64 - 10)
+                .collect::<Vec<_>>();
+            let t = MergeSortTree::new(&a);
+            for _ in 0..30 {
+                let (l, r) = (rng.below(len), rng.below(len));
+                let (l, r) = (l.min(r), l.max(r) + 1);
+                let mut sorted = a[l..r].to_vec();
+                sorted.sort_unstable();
+                let k = rng.below(r - l);
+                assert_eq!(t.kth_smallest(l, r, k), sorted[k]);
+                let v = rng.below(25) as i64 - 12;
+                assert_eq!(
+                    t.count_le(l, r, v),
+                    sorted.iter().filter(|&&y| y <= v).count()
+                );
+            }
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub mod bag_hash;
 pub mod dsu;
+pub mod euler_tour;
+pub mod hld;
+pub mod kruskal_tree;
+pub mod merge_sort_tree;
+pub mod monoid;
 pub mod multi_set;
 pub mod segment_tree;
@@ -0,0 +1,186 @@
+use crate::data_structure::segment_tree::SegmentTree;
+use crate::math::ratio::gcd;
+use cargo_snippet::snippet;
+
+#[snippet("monoid")]
+/// Associative binary operation with an identity element.
+pub trait Monoid {
+    type T;
+    fn combine(a: &Self::T, b: &Self::T) -> Self::T;
+    fn identity() -> Self::T;
+}
+
+#[snippet("monoid")]
+/// Types with the smallest and the largest value.
+pub trait Bounded {
+    fn min_value() -> Self;
+    fn max_value() -> Self;
+}
+
+#[snippet("monoid")]
+macro_rules! impl_bounded {
+    ($($t:ty),*) => {
+        $(impl Bounded for $t {
+            fn min_value() -> Self {
+                <$t>::min_value()
+            }
+            fn max_value() -> Self {
+                <$t>::max_value()
+            }
+        })*
+    };
+}
+
+#[snippet("monoid")]
+impl_bounded!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[snippet("monoid")]
+pub struct SumMonoid<T>(std::marker::PhantomData<T>);
+
+#[snippet("monoid")]
+impl<T: Copy + Default + std::ops::Add<Output = T>> Monoid for SumMonoid<T> {
+    type T = T;
+    fn combine(a: &T, b: &T) -> T {
+        *a + *b
+    }
+    fn identity() -> T {
+        T::default()
+    }
+}
+
+#[snippet("monoid")]
+pub struct MaxMonoid<T>(std::marker::PhantomData<T>);
+
+#[snippet("monoid")]
+impl<T: Copy + Ord + Bounded> Monoid for MaxMonoid<T> {
+    type T = T;
+    fn combine(a: &T, b: &T) -> T {
+        *std::cmp::max(a, b)
+    }
+    fn identity() -> T {
+        T::min_value()
+    }
+}
+
+#[snippet("monoid")]
+pub struct MinMonoid<T>(std::marker::PhantomData<T>);
+
+#[snippet("monoid")]
+impl<T: Copy + Ord + Bounded> Monoid for MinMonoid<T> {
+    type T = T;
+    fn combine(a: &T, b: &T) -> T {
+        *std::cmp::min(a, b)
+    }
+    fn identity() -> T {
+        T::max_value()
+    }
+}
+
+#[snippet("monoid")]
+pub struct GcdMonoid<T>(std::marker::PhantomData<T>);
+
+#[snippet("monoid")]
+#[snippet(include = "gcd")]
+impl<T> Monoid for GcdMonoid<T>
+where
+    T: Copy + Default + PartialEq + std::ops::Rem<Output = T> + std::ops::Add<Output = T>,
+{
+    type T = T;
+    fn combine(a: &T, b: &T) -> T {
+        gcd(*a, *b)
+    }
+    fn identity() -> T {
+        T::default()
+    }
+}
+
+#[snippet("monoid")]
+/// Segment tree over a named monoid, which can be stored without naming closure types.
+pub type MonoidSegmentTree<M> = SegmentTree<
+    <M as Monoid>::T,
+    fn(&<M as Monoid>::T, &<M as Monoid>::T) -> <M as Monoid>::T,
+    fn() -> <M as Monoid>::T,
+>;
+
+#[snippet("monoid")]
+#[snippet(include = "segment_tree")]
+impl<T: Clone> SegmentTree<T, fn(&T, &T) -> T, fn() -> T> {
+    pub fn with_monoid<M: Monoid<T = T>>(n: usize) -> Self {
+        Self::new(n, M::combine, M::identity)
+    }
+
+    /// Construct tree over monoid `M` from a given slice
+    pub fn from_slice_with_monoid<M: Monoid<T = T>>(slice: &[T]) -> Self {
+        Self::from_slice(slice, M::combine, M::identity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NODE: [i64; 7] = [12, 18, -91, 20, 6, 10, 970];
+
+    fn check<M: Monoid<T = i64>>(naive: impl Fn(&[i64]) -> i64) {
+        let mut t = MonoidSegmentTree::<M>::from_slice_with_monoid::<M>(&NODE);
+        let mut node = NODE;
+        t.update(3, 30);
+        node[3] = 30;
+        for i in 0..=node.len() {
+            for j in i..=node.len() {
+                assert_eq!(t.query(Some(i), Some(j)), naive(&node[i..j]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sum_monoid() {
+        check::<SumMonoid<i64>>(|a| a.iter().sum());
+    }
+
+    #[test]
+    fn test_max_monoid() {
+        check::<MaxMonoid<i64>>(|a| {
+            a.iter()
+                .cloned()
+                .max()
+                .unwrap_or(<i64 as Bounded>::min_value())
+        });
+    }
+
+    #[test]
+    fn test_min_monoid() {
+        check::<MinMonoid<i64>>(|a| {
+            a.iter()
+                .cloned()
+                .min()
+                .unwrap_or(<i64 as Bounded>::max_value())
+        });
+    }
+
+    #[test]
+    fn test_gcd_monoid() {
+        let node = [12_u64, 18, 30, 7, 14, 0, 21];
+        let t = SegmentTree::from_slice_with_monoid::<GcdMonoid<u64>>(&node);
+        assert_eq!(t.query_range(0..3), 6);
+        assert_eq!(t.query_range(3..5), 7);
+        assert_eq!(t.query_range(4..), 7);
+        assert_eq!(t.query_range(5..6), 0);
+        assert_eq!(t.query_range(..), 1);
+    }
+
+    #[test]
+    fn test_stored_in_struct_field() {
+        struct Solver {
+            tree: MonoidSegmentTree<MaxMonoid<u32>>,
+        }
+        let mut solver = Solver {
+            tree: SegmentTree::with_monoid::<MaxMonoid<u32>>(5),
+        };
+        assert_eq!(solver.tree.query(None, None), 0);
+        solver.tree.update(2, 8);
+        solver.tree.update(4, 3);
+        assert_eq!(solver.tree.query_range(..), 8);
+        assert_eq!(solver.tree.query_range(3..), 3);
+    }
+}
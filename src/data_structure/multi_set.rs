@@ -1,7 +1,7 @@
 use cargo_snippet::snippet;
 
 #[snippet("multi_set")]
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct MultiSet<T> {
     len: usize,
     freq: std::collections::BTreeMap<T, usize>,
@@ -82,6 +82,18 @@ impl<T: Ord> Default for MultiSet<T> {
     }
 }
 
+#[snippet("multi_set")]
+impl<T: std::hash::Hash + Ord> std::hash::Hash for MultiSet<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // `BTreeMap` iterates in key order, so equal multisets feed the same sequence.
+        self.freq.len().hash(state);
+        for (e, cnt) in self.freq.iter() {
+            e.hash(state);
+            cnt.hash(state);
+        }
+    }
+}
+
 #[snippet("multi_set")]
 impl<T: Ord + Clone> MultiSet<T> {
     pub fn new() -> Self {
@@ -150,6 +162,34 @@ impl<T: Ord + Clone> MultiSet<T> {
     pub fn iter(&self) -> Iter<T> {
         Iter::new(self)
     }
+
+    /// `k`-th (0-indexed) smallest element counting multiplicity among those in [`lo`, `hi`),
+    /// where `None` means unbounded. Time-complexity is linear in the number of distinct
+    /// elements scanned in the range.
+    pub fn nth_in_range(&self, lo: Option<&T>, hi: Option<&T>, k: usize) -> Option<&T> {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+        if let (Some(lo), Some(hi)) = (lo, hi) {
+            if lo >= hi {
+                return None;
+            }
+        }
+        let lo = match lo {
+            Some(lo) => Included(lo),
+            None => Unbounded,
+        };
+        let hi = match hi {
+            Some(hi) => Excluded(hi),
+            None => Unbounded,
+        };
+        let mut k = k;
+        for (e, &cnt) in self.freq.range((lo, hi)) {
+            if k < cnt {
+                return Some(e);
+            }
+            k -= cnt;
+        }
+        None
+    }
 }
 
 #[cfg(test)]
@@ -251,4 +291,69 @@ mod tests {
         assert_eq!(None, iter.next());
         assert_eq!(None, iter.next_back());
     }
+
+    #[test]
+    fn test_nth_in_range() {
+        let ms = MultiSet::from_slice(&[1, 1, 2, 3, 5]);
+        assert_eq!(ms.nth_in_range(None, None, 0), Some(&1));
+        assert_eq!(ms.nth_in_range(None, None, 1), Some(&1));
+        assert_eq!(ms.nth_in_range(None, None, 4), Some(&5));
+        assert_eq!(ms.nth_in_range(None, None, 5), None);
+        assert_eq!(ms.nth_in_range(Some(&2), None, 0), Some(&2));
+        assert_eq!(ms.nth_in_range(Some(&2), None, 2), Some(&5));
+        assert_eq!(ms.nth_in_range(Some(&0), Some(&3), 2), Some(&2));
+        assert_eq!(ms.nth_in_range(Some(&1), Some(&5), 3), Some(&3));
+        assert_eq!(ms.nth_in_range(None, Some(&2), 1), Some(&1));
+    }
+
+    #[test]
+    fn test_nth_in_range_with_too_few_elements() {
+        let ms = MultiSet::from_slice(&[1, 1, 2, 3, 5]);
+        assert_eq!(ms.nth_in_range(Some(&2), Some(&5), 2), None);
+        assert_eq!(ms.nth_in_range(Some(&4), Some(&5), 0), None);
+        assert_eq!(ms.nth_in_range(Some(&5), Some(&1), 0), None);
+    }
+
+    fn hash_of(ms: &MultiSet<i32>) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        ms.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_equal_multisets_have_same_hash() {
+        let a = MultiSet::from_slice(&[3, 1, 3, 2]);
+        let mut b = MultiSet::new();
+        for &e in [2, 3, 1, 3].iter() {
+            b.insert(e);
+        }
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        b.remove(&3);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_multi_set_as_hash_map_key() {
+        let mut memo = std::collections::HashMap::new();
+        memo.insert(MultiSet::from_slice(&[1, 1, 2]), 10);
+        memo.insert(MultiSet::from_slice(&[1, 2]), 20);
+        assert_eq!(memo.get(&MultiSet::from_slice(&[2, 1, 1])), Some(&10));
+        assert_eq!(memo.get(&MultiSet::from_slice(&[2, 1])), Some(&20));
+        assert_eq!(memo.get(&MultiSet::from_slice(&[2, 2, 1])), None);
+    }
+
+    #[test]
+    fn test_equal_multi_sets_collapse_in_hash_set() {
+        let mut seen = std::collections::HashSet::new();
+        let a = MultiSet::from_slice(&[5, 4, 5]);
+        let mut b = MultiSet::new();
+        b.insert(5);
+        b.insert(5);
+        b.insert(4);
+        assert!(seen.insert(a));
+        assert!(!seen.insert(b));
+        assert_eq!(seen.len(), 1);
+    }
 }
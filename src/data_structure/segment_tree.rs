@@ -31,8 +31,8 @@ where
 /// Abstract segment tree.
 impl<T, Op, Id> SegmentTree<T, Op, Id>
 where
-    T: Copy,
-    Op: Fn(T, T) -> T,
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
     Id: Fn() -> T,
 {
     pub fn new(n: usize, op: Op, id: Id) -> Self {
@@ -44,11 +44,9 @@ where
     /// Construct tree from a given slice
     pub fn from_slice(slice: &[T], op: Op, id: Id) -> Self {
         let mut tree = Self::new(slice.len(), op, id);
-        for (i, &x) in slice.iter().enumerate() {
-            tree.node[i + tree.n] = x;
-        }
+        tree.node[tree.n..tree.n + slice.len()].clone_from_slice(slice);
         for i in (1..tree.n).rev() {
-            tree.node[i] = (tree.op)(tree.node[i << 1], tree.node[i << 1 | 1]);
+            tree.node[i] = (tree.op)(&tree.node[i << 1], &tree.node[i << 1 | 1]);
         }
         tree
     }
@@ -60,7 +58,27 @@ where
         self.node[i] = x;
         while i > 1 {
             i >>= 1;
-            self.node[i] = (self.op)(self.node[i << 1], self.node[i << 1 | 1]);
+            self.node[i] = (self.op)(&self.node[i << 1], &self.node[i << 1 | 1]);
+        }
+    }
+
+    /// Set value of every element within range [`left`, `right`) to `x`.
+    pub fn fill_range(&mut self, left: usize, right: usize, x: T) {
+        assert!(left <= right && right <= self.n);
+        if left == right {
+            return;
+        }
+        let mut l = left + self.n;
+        let mut r = right - 1 + self.n;
+        for i in l..=r {
+            self.node[i] = x.clone();
+        }
+        while l > 1 {
+            l >>= 1;
+            r >>= 1;
+            for i in l..=r {
+                self.node[i] = (self.op)(&self.node[i << 1], &self.node[i << 1 | 1]);
+            }
         }
     }
 
@@ -73,17 +91,33 @@ where
         let mut res_r = (self.id)();
         while l < r {
             if l & 1 == 1 {
-                res_l = (self.op)(res_l, self.node[l]);
+                res_l = (self.op)(&res_l, &self.node[l]);
                 l += 1;
             }
             if r & 1 == 1 {
                 r -= 1;
-                res_r = (self.op)(self.node[r], res_r);
+                res_r = (self.op)(&self.node[r], &res_r);
             }
             l >>= 1;
             r >>= 1;
         }
-        (self.op)(res_l, res_r)
+        (self.op)(&res_l, &res_r)
+    }
+
+    /// Query value `op` acted on `range` such as `2..5`, `..=4` or `..`.
+    pub fn query_range<R: std::ops::RangeBounds<usize>>(&self, range: R) -> T {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+        let left = match range.start_bound() {
+            Included(&l) => Some(l),
+            Excluded(&l) => Some(l + 1),
+            Unbounded => None,
+        };
+        let right = match range.end_bound() {
+            Included(&r) => Some(r + 1),
+            Excluded(&r) => Some(r),
+            Unbounded => None,
+        };
+        self.query(left, right)
     }
 }
 
@@ -121,7 +155,7 @@ mod tests {
     #[test]
     fn test_whole_query() {
         let node = [1, 2, -91, 20, 5, 10, 970];
-        let tree = SegmentTree::from_slice(&node, std::cmp::min, || *node.iter().max().unwrap());
+        let tree = SegmentTree::from_slice(&node, |a, b| *a.min(b), || *node.iter().max().unwrap());
         let whole_min = tree.query(None, None);
         assert_eq!(whole_min, -91);
         let right_min = tree.query(Some(3), None);
@@ -129,4 +163,46 @@ mod tests {
         let left_min = tree.query(None, Some(2));
         assert_eq!(left_min, 1);
     }
+
+    #[test]
+    fn test_fill_range() {
+        let mut node = vec![1, 2, -91, 20, 5, 10, 970];
+        let mut t = SegmentTree::from_slice(&node, |a, b| a + b, || 0);
+        t.fill_range(2, 5, 7);
+        for x in &mut node[2..5] {
+            *x = 7;
+        }
+        t.fill_range(6, 6, 100);
+        for i in 0..=node.len() {
+            for j in i..=node.len() {
+                assert_eq!(t.query(Some(i), Some(j)), node[i..j].iter().sum::<i32>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_query_range() {
+        let node = [1, 2, -91, 20, 5, 10, 970];
+        let t = SegmentTree::from_slice(&node, |a, b| a + b, || 0);
+        assert_eq!(t.query_range(2..5), t.query(Some(2), Some(5)));
+        assert_eq!(t.query_range(2..=4), t.query(Some(2), Some(5)));
+        assert_eq!(t.query_range(..), t.query(None, None));
+        assert_eq!(t.query_range(3..), t.query(Some(3), None));
+        assert_eq!(t.query_range(..4), t.query(None, Some(4)));
+        assert_eq!(t.query_range(4..4), 0);
+    }
+
+    #[test]
+    fn test_non_copy_nodes() {
+        let node = (0..7).map(|x| vec![x as i64]).collect::<Vec<_>>();
+        let concat = |a: &Vec<i64>, b: &Vec<i64>| [&a[..], &b[..]].concat();
+        let mut t = SegmentTree::from_slice(&node, concat, Vec::new);
+        assert_eq!(t.query_range(2..5), vec![2, 3, 4]);
+        assert_eq!(t.query_range(..), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(t.query_range(3..3), vec![]);
+        t.update(3, vec![30, 31]);
+        assert_eq!(t.query_range(1..=4), vec![1, 2, 30, 31, 4]);
+        t.fill_range(5, 7, vec![]);
+        assert_eq!(t.query_range(4..), vec![4]);
+    }
 }
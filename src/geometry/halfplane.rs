@@ -0,0 +1,213 @@
+use cargo_snippet::snippet;
+
+#[snippet("halfplane_intersection")]
+const HP_EPS: f64 = 1e-9;
+
+#[snippet("halfplane_intersection")]
+/// Intersection point of lines `(x1, y1, x2, y2)` `a` and `b`, which must not be parallel.
+fn line_intersection(a: (f64, f64, f64, f64), b: (f64, f64, f64, f64)) -> (f64, f64) {
+    let (dax, day) = (a.2 - a.0, a.3 - a.1);
+    let (dbx, dby) = (b.2 - b.0, b.3 - b.1);
+    let t = ((b.0 - a.0) * dby - (b.1 - a.1) * dbx) / (dax * dby - day * dbx);
+    (a.0 + dax * t, a.1 + day * t)
+}
+
+#[snippet("halfplane_intersection")]
+/// Tests if `p` is strictly outside of the left half-plane of `h` with tolerance.
+fn is_out(h: (f64, f64, f64, f64), p: (f64, f64)) -> bool {
+    (h.2 - h.0) * (p.1 - h.1) - (h.3 - h.1) * (p.0 - h.0) < -HP_EPS
+}
+
+#[snippet("halfplane_intersection")]
+/// Tests if `p` and `q` coincide with tolerance relative to their magnitude.
+fn is_same_point(p: (f64, f64), q: (f64, f64)) -> bool {
+    let scale = 1. + p.0.abs().max(p.1.abs());
+    (p.0 - q.0).abs() < HP_EPS * scale && (p.1 - q.1).abs() < HP_EPS * scale
+}
+
+#[snippet("halfplane_intersection")]
+/// Intersection of `halfplanes` with time-complexity `O(n log n)`.
+///
+/// Each `(x1, y1, x2, y2)` is the left half-plane of the directed line from `(x1, y1)` to `(x2, y2)`.
+/// Returns vertices of the intersection in counterclockwise order,
+/// or `None` if it is empty, degenerate or unbounded.
+/// Coordinates are assumed to be within `1e9` in absolute value.
+pub fn halfplane_intersection(halfplanes: &[(f64, f64, f64, f64)]) -> Option<Vec<(f64, f64)>> {
+    const INF: f64 = 1e9;
+    let n = halfplanes.len();
+    // Bounding box detects unbounded intersections.
+    let mut hs = halfplanes.to_vec();
+    hs.push((-INF, -INF, INF, -INF));
+    hs.push((INF, -INF, INF, INF));
+    hs.push((INF, INF, -INF, INF));
+    hs.push((-INF, INF, -INF, -INF));
+    let angle = |h: &(f64, f64, f64, f64)| (h.3 - h.1).atan2(h.2 - h.0);
+    let mut ord = (0..hs.len()).collect::<Vec<_>>();
+    ord.sort_by(|&i, &j| angle(&hs[i]).partial_cmp(&angle(&hs[j])).unwrap());
+
+    let mut dq = std::collections::VecDeque::<usize>::new();
+    for &i in &ord {
+        let h = hs[i];
+        while dq.len() > 1
+            && is_out(
+                h,
+                line_intersection(hs[dq[dq.len() - 1]], hs[dq[dq.len() - 2]]),
+            )
+        {
+            dq.pop_back();
+        }
+        while dq.len() > 1 && is_out(h, line_intersection(hs[dq[0]], hs[dq[1]])) {
+            dq.pop_front();
+        }
+        if let Some(&j) = dq.back() {
+            let b = hs[j];
+            let (dhx, dhy) = (h.2 - h.0, h.3 - h.1);
+            let (dbx, dby) = (b.2 - b.0, b.3 - b.1);
+            if (dhx * dby - dhy * dbx).abs() < HP_EPS {
+                if dhx * dbx + dhy * dby < 0. {
+                    return None;
+                }
+                if is_out(h, (b.0, b.1)) {
+                    dq.pop_back();
+                } else {
+                    continue;
+                }
+            }
+        }
+        dq.push_back(i);
+    }
+    while dq.len() > 2
+        && is_out(
+            hs[dq[0]],
+            line_intersection(hs[dq[dq.len() - 1]], hs[dq[dq.len() - 2]]),
+        )
+    {
+        dq.pop_back();
+    }
+    while dq.len() > 2
+        && is_out(
+            hs[dq[dq.len() - 1]],
+            line_intersection(hs[dq[0]], hs[dq[1]]),
+        )
+    {
+        dq.pop_front();
+    }
+    if dq.len() < 3 || dq.iter().any(|&i| i >= n) {
+        return None;
+    }
+    let k = dq.len();
+    let mut res = Vec::<(f64, f64)>::with_capacity(k);
+    for i in 0..k {
+        let p = line_intersection(hs[dq[i]], hs[dq[(i + 1) % k]]);
+        match res.last() {
+            Some(&q) if is_same_point(p, q) => {}
+            _ => res.push(p),
+        }
+    }
+    while res.len() > 1 && is_same_point(res[0], res[res.len() - 1]) {
+        res.pop();
+    }
+    if res.len() < 3 {
+        return None;
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_area(poly: &[(f64, f64)]) -> f64 {
+        let k = poly.len();
+        (0..k)
+            .map(|i| {
+                let (a, b) = (poly[i], poly[(i + 1) % k]);
+                a.0 * b.1 - a.1 * b.0
+            })
+            .sum::<f64>()
+            / 2.
+    }
+
+    fn contains_vertex(poly: &[(f64, f64)], p: (f64, f64)) -> bool {
+        poly.iter()
+            .any(|q| (q.0 - p.0).abs() < 1e-6 && (q.1 - p.1).abs() < 1e-6)
+    }
+
+    #[test]
+    fn test_square() {
+        let hs = [
+            (0., 0., 1., 0.),
+            (1., 0., 1., 1.),
+            (1., 1., 0., 1.),
+            (0., 1., 0., 0.),
+        ];
+        let poly = halfplane_intersection(&hs).unwrap();
+        assert_eq!(poly.len(), 4);
+        for &p in &[(0., 0.), (1., 0.), (1., 1.), (0., 1.)] {
+            assert!(contains_vertex(&poly, p));
+        }
+        assert!((signed_area(&poly) - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_empty() {
+        let hs = [
+            (0., 0., 1., 0.),
+            (1., 0., 1., 1.),
+            (1., 1., 0., 1.),
+            (0., 1., 0., 0.),
+            // x >= 2
+            (2., 1., 2., 0.),
+        ];
+        assert_eq!(halfplane_intersection(&hs), None);
+    }
+
+    #[test]
+    fn test_triangle() {
+        let hs = [(0., 0., 4., 0.), (4., 0., 0., 4.), (0., 4., 0., 0.)];
+        let poly = halfplane_intersection(&hs).unwrap();
+        assert_eq!(poly.len(), 3);
+        for &p in &[(0., 0.), (4., 0.), (0., 4.)] {
+            assert!(contains_vertex(&poly, p));
+        }
+        assert!((signed_area(&poly) - 8.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_single_point() {
+        // x >= 0, y >= 0 and x + y <= 0
+        let hs = [(0., 0., 1., 0.), (0., 1., 0., 0.), (1., -1., -1., 1.)];
+        assert_eq!(halfplane_intersection(&hs), None);
+        let hs = [
+            (5., 2., 2., 10.),
+            (6., 4., 5., -8.),
+            (-3., -6., -5., -8.),
+            (-9., 2., -6., -2.),
+            (-8., -6., -3., -2.),
+            (-1., 8., 0., 1.),
+            (0., 7., -3., 10.),
+            (-6., -7., 4., -2.),
+        ];
+        assert_eq!(halfplane_intersection(&hs), None);
+    }
+
+    #[test]
+    fn test_segment() {
+        // 0 <= x <= 1 and y == 0
+        let hs = [
+            (0., 0., 1., 0.),
+            (1., 0., 0., 0.),
+            (1., -1., 1., 1.),
+            (0., 1., 0., -1.),
+        ];
+        assert_eq!(halfplane_intersection(&hs), None);
+    }
+
+    #[test]
+    fn test_unbounded() {
+        // x >= 0 and y >= 0
+        let hs = [(0., 0., 1., 0.), (0., 1., 0., 0.)];
+        assert_eq!(halfplane_intersection(&hs), None);
+        assert_eq!(halfplane_intersection(&[]), None);
+    }
+}
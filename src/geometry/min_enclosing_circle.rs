@@ -0,0 +1,154 @@
+use cargo_snippet::snippet;
+
+#[snippet("min_enclosing_circle")]
+const MEC_EPS: f64 = 1e-9;
+
+#[snippet("min_enclosing_circle")]
+/// Circle `(cx, cy, radius)` consisting of a single point.
+pub fn circle_from_1(a: (f64, f64)) -> (f64, f64, f64) {
+    (a.0, a.1, 0.)
+}
+
+#[snippet("min_enclosing_circle")]
+/// Circle `(cx, cy, radius)` whose diameter is segment `ab`.
+pub fn circle_from_2(a: (f64, f64), b: (f64, f64)) -> (f64, f64, f64) {
+    let (cx, cy) = ((a.0 + b.0) / 2., (a.1 + b.1) / 2.);
+    (cx, cy, (a.0 - cx).hypot(a.1 - cy))
+}
+
+#[snippet("min_enclosing_circle")]
+/// Smallest circle `(cx, cy, radius)` passing through or containing `a`, `b` and `c`.
+///
+/// The circumcircle is returned unless the points are collinear,
+/// in which case the circle whose diameter is the longest pair is returned.
+pub fn circle_from_3(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> (f64, f64, f64) {
+    let (bx, by) = (b.0 - a.0, b.1 - a.1);
+    let (cx, cy) = (c.0 - a.0, c.1 - a.1);
+    let d = 2. * (bx * cy - by * cx);
+    if d.abs() < MEC_EPS {
+        let candidates = [
+            circle_from_2(a, b),
+            circle_from_2(b, c),
+            circle_from_2(a, c),
+        ];
+        return candidates
+            .iter()
+            .cloned()
+            .fold(candidates[0], |acc, x| if x.2 > acc.2 { x } else { acc });
+    }
+    let b2 = bx * bx + by * by;
+    let c2 = cx * cx + cy * cy;
+    let ux = (cy * b2 - by * c2) / d;
+    let uy = (bx * c2 - cx * b2) / d;
+    (ux + a.0, uy + a.1, ux.hypot(uy))
+}
+
+#[snippet("min_enclosing_circle")]
+/// Tests if `p` is inside or on circle `c` with tolerance.
+pub fn is_in_circle(c: (f64, f64, f64), p: (f64, f64)) -> bool {
+    (p.0 - c.0).hypot(p.1 - c.1) <= c.2 + MEC_EPS
+}
+
+#[snippet("min_enclosing_circle")]
+/// Minimum enclosing circle `(cx, cy, radius)` of `points` by Welzl's algorithm
+/// with expected time-complexity `O(n)`. Returns `(0, 0, 0)` for empty `points`.
+pub fn min_enclosing_circle(points: &[(f64, f64)]) -> (f64, f64, f64) {
+    if points.is_empty() {
+        return (0., 0., 0.);
+    }
+    // Shuffle by xorshift to guarantee expected linear time.
+    let mut p = points.to_vec();
+    let mut seed: u64 = 88_172_645_463_325_252;
+    for i in (1..p.len()).rev() {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        p.swap(i, (seed % (i as u64 + 1)) as usize);
+    }
+
+    let mut c = circle_from_1(p[0]);
+    for i in 1..p.len() {
+        if is_in_circle(c, p[i]) {
+            continue;
+        }
+        c = circle_from_1(p[i]);
+        for j in 0..i {
+            if is_in_circle(c, p[j]) {
+                continue;
+            }
+            c = circle_from_2(p[i], p[j]);
+            for k in 0..j {
+                if !is_in_circle(c, p[k]) {
+                    c = circle_from_3(p[i], p[j], p[k]);
+                }
+            }
+        }
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_circle_eq(a: (f64, f64, f64), b: (f64, f64, f64)) {
+        assert!((a.0 - b.0).abs() < 1e-6, "{:?} != {:?}", a, b);
+        assert!((a.1 - b.1).abs() < 1e-6, "{:?} != {:?}", a, b);
+        assert!((a.2 - b.2).abs() < 1e-6, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_trivial_cases() {
+        assert_circle_eq(min_enclosing_circle(&[]), (0., 0., 0.));
+        assert_circle_eq(min_enclosing_circle(&[(1., 2.)]), (1., 2., 0.));
+        assert_circle_eq(min_enclosing_circle(&[(0., 0.), (2., 0.)]), (1., 0., 1.));
+    }
+
+    #[test]
+    fn test_collinear() {
+        assert_circle_eq(
+            min_enclosing_circle(&[(0., 0.), (3., 3.), (1., 1.)]),
+            (1.5, 1.5, 4.5_f64.sqrt()),
+        );
+        assert_circle_eq(circle_from_3((0., 0.), (4., 0.), (1., 0.)), (2., 0., 2.));
+    }
+
+    #[test]
+    fn test_unit_circle() {
+        let points = (0..100)
+            .map(|i| {
+                let t = i as f64 * 0.37;
+                (t.cos(), t.sin())
+            })
+            .collect::<Vec<_>>();
+        let c = min_enclosing_circle(&points);
+        assert!((c.2 - 1.).abs() < 1e-6);
+        assert!(c.0.abs() < 1e-6 && c.1.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_three_points() {
+        // Right triangle: circumcircle centered at the midpoint of the hypotenuse.
+        assert_circle_eq(
+            min_enclosing_circle(&[(0., 0.), (4., 0.), (0., 3.)]),
+            (2., 1.5, 2.5),
+        );
+        // Obtuse triangle: determined by the longest edge only.
+        assert_circle_eq(
+            min_enclosing_circle(&[(0., 0.), (10., 0.), (5., 1.)]),
+            (5., 0., 5.),
+        );
+        // Acute triangle: circumcircle.
+        let c = min_enclosing_circle(&[(0., 0.), (2., 0.), (1., 3.0_f64.sqrt())]);
+        assert_circle_eq(c, (1., 3.0_f64.sqrt() / 3., 2. / 3.0_f64.sqrt()));
+    }
+
+    #[test]
+    fn test_contains_all_points() {
+        let points = (0..200)
+            .map(|i| ((i * 37 % 101) as f64, (i * 53 % 97) as f64 * 0.5))
+            .collect::<Vec<_>>();
+        let c = min_enclosing_circle(&points);
+        assert!(points.iter().all(|&p| is_in_circle(c, p)));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod halfplane;
+pub mod min_enclosing_circle;
+pub mod point;
+pub mod polar_sort;
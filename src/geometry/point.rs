@@ -0,0 +1,155 @@
+use cargo_snippet::snippet;
+
+#[snippet("point")]
+/// 2D point or vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[snippet("point")]
+impl<T> Point<T>
+where
+    T: Copy
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<Output = T>
+        + std::ops::Neg<Output = T>,
+{
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Signed area of the parallelogram spanned by `self` and `other`,
+    /// which is positive if `other` is counterclockwise from `self`.
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn norm_sq(&self) -> T {
+        self.dot(self)
+    }
+}
+
+#[snippet("point")]
+impl Point<f64> {
+    pub fn norm(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Unit vector in the same direction.
+    pub fn normalize(&self) -> Self {
+        let r = self.norm();
+        Self::new(self.x / r, self.y / r)
+    }
+
+    /// Argument in radians within [-pi, pi].
+    pub fn angle(&self) -> f64 {
+        self.y.atan2(self.x)
+    }
+}
+
+#[snippet("point")]
+impl<T: std::ops::Add<Output = T>> std::ops::Add for Point<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Point {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+#[snippet("point")]
+impl<T: std::ops::Sub<Output = T>> std::ops::Sub for Point<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+#[snippet("point")]
+impl<T: std::ops::Neg<Output = T>> std::ops::Neg for Point<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Point {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+#[snippet("point")]
+impl<T: Copy + std::ops::Mul<Output = T>> std::ops::Mul<T> for Point<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Point {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+#[snippet("point")]
+/// Orientation of `a`, `b`, `c`.
+/// `1` if counterclockwise, `-1` if clockwise and `0` if collinear.
+pub fn ccw(a: &Point<i64>, b: &Point<i64>, c: &Point<i64>) -> i64 {
+    (*b - *a).cross(&(*c - *a)).signum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(3, -1);
+        assert_eq!(a + b, Point::new(4, 1));
+        assert_eq!(a - b, Point::new(-2, 3));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a * 3, Point::new(3, 6));
+    }
+
+    #[test]
+    fn test_dot_is_zero_for_perpendicular() {
+        let a = Point::new(2, 3);
+        let b = Point::new(-3, 2);
+        assert_eq!(a.dot(&b), 0);
+        assert_eq!(a.norm_sq(), 13);
+    }
+
+    #[test]
+    fn test_cross_sign() {
+        let a = Point::new(1, 0);
+        let b = Point::new(0, 1);
+        assert!(a.cross(&b) > 0);
+        assert!(b.cross(&a) < 0);
+        assert_eq!(a.cross(&(a * 5)), 0);
+    }
+
+    #[test]
+    fn test_ccw() {
+        let (a, b) = (Point::new(0, 0), Point::new(2, 0));
+        assert_eq!(ccw(&a, &b, &Point::new(1, 1)), 1);
+        assert_eq!(ccw(&a, &b, &Point::new(1, -1)), -1);
+        assert_eq!(ccw(&a, &b, &Point::new(5, 0)), 0);
+    }
+
+    #[test]
+    fn test_float_operations() {
+        let a = Point::new(3., 4.);
+        assert!((a.norm() - 5.).abs() < 1e-9);
+        let u = a.normalize();
+        assert!((u.x - 0.6).abs() < 1e-9 && (u.y - 0.8).abs() < 1e-9);
+        assert!((Point::new(0., 2.).angle() - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,83 @@
+use cargo_snippet::snippet;
+
+#[snippet("polar_sort")]
+/// Compare `a` and `b` by polar angle around `pivot` in [`0`, `2π`)
+/// starting from the positive x-axis, and then by distance from `pivot`.
+///
+/// Only integer cross products are used, so coordinates of the differences
+/// are expected to be within about `1e9` in absolute value.
+pub fn polar_angle_cmp(a: (i64, i64), b: (i64, i64), pivot: (i64, i64)) -> std::cmp::Ordering {
+    let (ax, ay) = (a.0 - pivot.0, a.1 - pivot.1);
+    let (bx, by) = (b.0 - pivot.0, b.1 - pivot.1);
+    // `0` for angles in [0, π), `1` for [π, 2π)
+    let half = |x: i64, y: i64| if y > 0 || (y == 0 && x >= 0) { 0 } else { 1 };
+    half(ax, ay)
+        .cmp(&half(bx, by))
+        .then_with(|| 0.cmp(&(ax * by - ay * bx)))
+        .then_with(|| (ax * ax + ay * ay).cmp(&(bx * bx + by * by)))
+}
+
+#[snippet("polar_sort")]
+/// Sort `points` by `polar_angle_cmp` around `pivot`.
+pub fn polar_sort(points: &mut [(i64, i64)], pivot: (i64, i64)) {
+    points.sort_by(|&a, &b| polar_angle_cmp(a, b, pivot));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_eight_directions() {
+        let mut points = vec![
+            (-1, -1),
+            (0, 1),
+            (1, -1),
+            (-1, 0),
+            (1, 1),
+            (0, -1),
+            (1, 0),
+            (-1, 1),
+        ];
+        polar_sort(&mut points, (0, 0));
+        assert_eq!(
+            points,
+            vec![
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+                (0, -1),
+                (1, -1)
+            ]
+        );
+        for w in points.windows(2) {
+            let angle = |p: (i64, i64)| (p.1 as f64).atan2(p.0 as f64);
+            let mut diff = angle(w[1]) - angle(w[0]);
+            if diff < 0. {
+                diff += 2. * std::f64::consts::PI;
+            }
+            assert!((diff - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_positive_x_axis_and_distance() {
+        let pivot = (2, 3);
+        let mut points = vec![(3, 2), (5, 3), (2, 3), (3, 3), (2, 5), (1, 3)];
+        polar_sort(&mut points, pivot);
+        assert_eq!(points, vec![(2, 3), (3, 3), (5, 3), (2, 5), (1, 3), (3, 2)]);
+    }
+
+    #[test]
+    fn test_comparator() {
+        let o = (0, 0);
+        assert_eq!(polar_angle_cmp((1, 0), (1, -1), o), Ordering::Less);
+        assert_eq!(polar_angle_cmp((-1, 1), (-1, -1), o), Ordering::Less);
+        assert_eq!(polar_angle_cmp((2, 2), (1, 1), o), Ordering::Greater);
+        assert_eq!(polar_angle_cmp((3, 4), (3, 4), o), Ordering::Equal);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::misc::{adjacent_grids, DIR4, DIR8};
+use cargo_snippet::snippet;
+
+#[snippet("grid", include = "adjacent_grids")]
+/// Two-dimensional grid stored in a flat vector in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    data: Vec<T>,
+}
+
+#[snippet("grid", include = "adjacent_grids")]
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, init: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec(height, width, vec![init; height * width])
+    }
+
+    /// Constructs a grid from rows of the same length.
+    pub fn from_rows(rows: &[Vec<T>]) -> Self
+    where
+        T: Clone,
+    {
+        let width = rows.first().map_or(0, |r| r.len());
+        for (i, r) in rows.iter().enumerate() {
+            assert!(
+                r.len() == width,
+                "Grid: row {} has length {} but row 0 has length {}",
+                i,
+                r.len(),
+                width
+            );
+        }
+        Self::from_vec(rows.len(), width, rows.concat())
+    }
+
+    /// Constructs a grid from `data` in row-major order.
+    pub fn from_vec(height: usize, width: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), height * width);
+        Self {
+            height,
+            width,
+            data,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if i < self.height && j < self.width {
+            Some(&self.data[i * self.width + j])
+        } else {
+            None
+        }
+    }
+
+    /// Rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |i| &self.data[i * self.width..(i + 1) * self.width])
+    }
+
+    /// Neighbors of `(i, j)` in 4 directions within the grid.
+    pub fn neighbors4(&self, i: usize, j: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        adjacent_grids(i, j, self.height, self.width, &DIR4).map(move |p| (p, &self[p]))
+    }
+
+    /// Neighbors of `(i, j)` in 8 directions within the grid.
+    pub fn neighbors8(&self, i: usize, j: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        adjacent_grids(i, j, self.height, self.width, &DIR8).map(move |p| (p, &self[p]))
+    }
+
+    /// First cell in row-major order satisfying `pred`.
+    pub fn find<F: Fn(&T) -> bool>(&self, pred: F) -> Option<(usize, usize)> {
+        self.data
+            .iter()
+            .position(pred)
+            .map(|k| (k / self.width, k % self.width))
+    }
+
+    /// All cells in row-major order satisfying `pred`.
+    pub fn find_all<F: Fn(&T) -> bool>(&self, pred: F) -> Vec<(usize, usize)> {
+        (0..self.data.len())
+            .filter(|&k| pred(&self.data[k]))
+            .map(|k| (k / self.width, k % self.width))
+            .collect()
+    }
+
+    /// The only cell satisfying `pred`, or `None` if there are zero or multiple such cells.
+    pub fn find_unique<F: Fn(&T) -> bool>(&self, pred: F) -> Option<(usize, usize)> {
+        let mut cells = (0..self.data.len()).filter(|&k| pred(&self.data[k]));
+        match (cells.next(), cells.next()) {
+            (Some(k), None) => Some((k / self.width, k % self.width)),
+            _ => None,
+        }
+    }
+
+    /// Number of cells satisfying `pred`.
+    pub fn count<F: Fn(&T) -> bool>(&self, pred: F) -> usize {
+        self.data.iter().filter(|x| pred(x)).count()
+    }
+}
+
+#[snippet("grid", include = "adjacent_grids")]
+impl<T: Clone> Grid<T> {
+    /// `height` by `width` grid whose cell `(i, j)` is a copy of `self[f(i, j)]`.
+    fn remap<F: Fn(usize, usize) -> (usize, usize)>(
+        &self,
+        height: usize,
+        width: usize,
+        f: F,
+    ) -> Self {
+        let data = (0..height * width)
+            .map(|k| self[f(k / width, k % width)].clone())
+            .collect();
+        Self::from_vec(height, width, data)
+    }
+
+    /// Grid reflected across the main diagonal, of size `width` by `height`.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.width, self.height, |i, j| (j, i))
+    }
+
+    /// Grid rotated by 90 degrees clockwise, of size `width` by `height`.
+    pub fn rotate_cw(&self) -> Self {
+        let h = self.height;
+        self.remap(self.width, h, |i, j| (h - 1 - j, i))
+    }
+
+    /// Grid rotated by 90 degrees counterclockwise, of size `width` by `height`.
+    pub fn rotate_ccw(&self) -> Self {
+        let w = self.width;
+        self.remap(w, self.height, |i, j| (j, w - 1 - i))
+    }
+
+    /// Grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        self.remap(self.height, w, |i, j| (i, w - 1 - j))
+    }
+
+    /// Grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        self.remap(h, self.width, |i, j| (h - 1 - i, j))
+    }
+
+    /// Distinct images under the 8 rotations and reflections, starting with `self`.
+    pub fn orientations(&self) -> Vec<Self>
+    where
+        T: PartialEq,
+    {
+        let mut res: Vec<Self> = vec![];
+        let mut g = self.clone();
+        for _ in 0..4 {
+            for h in [g.clone(), g.flip_horizontal()].iter() {
+                if !res.contains(h) {
+                    res.push(h.clone());
+                }
+            }
+            g = g.rotate_cw();
+        }
+        res
+    }
+}
+
+#[snippet("grid", include = "adjacent_grids")]
+impl Grid<u8> {
+    /// Constructs a grid of bytes from lines of the same length.
+    pub fn from_lines(lines: &[&str]) -> Self {
+        let width = lines.first().map_or(0, |l| l.len());
+        for (i, l) in lines.iter().enumerate() {
+            assert!(
+                l.len() == width,
+                "Grid: row {} has length {} but row 0 has length {}",
+                i,
+                l.len(),
+                width
+            );
+        }
+        Self::from_vec(lines.len(), width, lines.concat().into_bytes())
+    }
+}
+
+#[snippet("grid", include = "adjacent_grids")]
+impl Grid<char> {
+    /// Constructs a grid of chars from lines of the same number of chars.
+    pub fn from_char_lines(lines: &[&str]) -> Self {
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        for (i, l) in lines.iter().enumerate() {
+            assert!(
+                l.chars().count() == width,
+                "Grid: row {} has length {} but row 0 has length {}",
+                i,
+                l.chars().count(),
+                width
+            );
+        }
+        Self::from_vec(
+            lines.len(),
+            width,
+            lines.iter().flat_map(|l| l.chars()).collect(),
+        )
+    }
+}
+
+#[snippet("grid", include = "adjacent_grids")]
+impl<T> std::ops::Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(
+            i < self.height && j < self.width,
+            "Grid: index ({}, {}) out of bounds for {}x{} grid",
+            i,
+            j,
+            self.height,
+            self.width
+        );
+        &self.data[i * self.width + j]
+    }
+}
+
+#[snippet("grid", include = "adjacent_grids")]
+impl<T> std::ops::IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(
+            i < self.height && j < self.width,
+            "Grid: index ({}, {}) out of bounds for {}x{} grid",
+            i,
+            j,
+            self.height,
+            self.width
+        );
+        &mut self.data[i * self.width + j]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<u8> {
+        Grid::from_lines(&["S.#", ".##", "..G"])
+    }
+
+    #[test]
+    fn test_from_lines_and_index() {
+        let mut g = maze();
+        assert_eq!((g.height(), g.width()), (3, 3));
+        assert_eq!(g[(0, 0)], b'S');
+        assert_eq!(g[(1, 2)], b'#');
+        g[(1, 0)] = b'#';
+        assert_eq!(g.get(1, 0), Some(&b'#'));
+        assert_eq!(g.get(3, 0), None);
+        assert_eq!(g.find(|&c| c == b'G'), Some((2, 2)));
+        assert_eq!(g.find(|&c| c == b'X'), None);
+        let rows = g.rows().collect::<Vec<_>>();
+        assert_eq!(rows, vec![&b"S.#"[..], b"###", b"..G"]);
+    }
+
+    #[test]
+    fn test_neighbors_at_corner() {
+        let g = maze();
+        let mut n4 = g.neighbors4(0, 0).collect::<Vec<_>>();
+        n4.sort_unstable();
+        assert_eq!(n4, vec![((0, 1), &b'.'), ((1, 0), &b'.')]);
+        let mut n8 = g.neighbors8(2, 2).map(|(p, _)| p).collect::<Vec<_>>();
+        n8.sort_unstable();
+        assert_eq!(n8, vec![(1, 1), (1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let g = Grid::from_char_lines(&["αβ", "γδ"]);
+        assert_eq!(g.width(), 2);
+        assert_eq!(g[(1, 0)], 'γ');
+        assert_eq!(Grid::new(2, 3, 0).rows().count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "index (1, 3) out of bounds for 3x3 grid")]
+    fn test_out_of_bounds() {
+        let _ = maze()[(1, 3)];
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let g = Grid::from_lines(&["abc", "def"]);
+        assert_eq!(g.rotate_cw(), Grid::from_lines(&["da", "eb", "fc"]));
+        assert_eq!(g.rotate_ccw(), Grid::from_lines(&["cf", "be", "ad"]));
+        assert_eq!(g.flip_horizontal(), Grid::from_lines(&["cba", "fed"]));
+        assert_eq!(g.flip_vertical(), Grid::from_lines(&["def", "abc"]));
+        assert_eq!(g.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), g);
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        assert_eq!(
+            g.rotate_cw().rotate_cw(),
+            g.flip_horizontal().flip_vertical()
+        );
+    }
+
+    #[test]
+    fn test_transpose() {
+        let g = Grid::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        let t = g.transpose();
+        assert_eq!((t.height(), t.width()), (3, 2));
+        assert_eq!(t, Grid::from_vec(3, 2, vec![1, 4, 2, 5, 3, 6]));
+        assert_eq!(t.transpose(), g);
+    }
+
+    #[test]
+    fn test_orientations() {
+        let g = Grid::from_lines(&["ab", "c."]);
+        let all = g.orientations();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[0], g);
+        assert!(all.contains(&g.transpose()));
+        assert_eq!(Grid::from_lines(&["#.", ".."]).orientations().len(), 4);
+        assert_eq!(Grid::from_lines(&["##", "#."]).orientations().len(), 4);
+        assert_eq!(Grid::from_lines(&["###", "#.#"]).orientations().len(), 4);
+        assert_eq!(
+            Grid::from_lines(&[".#.", "###", ".#."])
+                .orientations()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_find_all_and_unique() {
+        let g = Grid::from_lines(&["S.#.", ".G.S", "#..."]);
+        assert_eq!(g.find_all(|&c| c == b'S'), vec![(0, 0), (1, 3)]);
+        assert_eq!(g.find_all(|&c| c == b'X'), vec![]);
+        assert_eq!(g.find_unique(|&c| c == b'G'), Some((1, 1)));
+        assert_eq!(g.find_unique(|&c| c == b'S'), None);
+        assert_eq!(g.find_unique(|&c| c == b'X'), None);
+        assert_eq!(g.count(|&c| c == b'.'), 7);
+        assert_eq!(g.count(|&c| c == b'#'), 2);
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let rows = vec![b"S.".to_vec(), b".G".to_vec()];
+        let g = Grid::from_rows(&rows);
+        assert_eq!(g.find_unique(|&c| c == b'G'), Some((1, 1)));
+        assert_eq!(Grid::<u8>::from_rows(&[]).count(|_| true), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 has length 3 but row 0 has length 2")]
+    fn test_from_rows_jagged() {
+        Grid::from_rows(&[vec![0, 1], vec![2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 has length 1 but row 0 has length 3")]
+    fn test_from_lines_jagged() {
+        Grid::from_lines(&["S.#", ".", "..G"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 has length 3 but row 0 has length 2")]
+    fn test_from_char_lines_jagged() {
+        Grid::from_char_lines(&["αβ", "γ.δ"]);
+    }
+}
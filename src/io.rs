@@ -0,0 +1,218 @@
+use cargo_snippet::snippet;
+
+#[snippet("scanner")]
+/// Parses `token`, panicking with the token and the target type if it is invalid.
+fn parse_token<T: std::str::FromStr>(token: &[u8]) -> T {
+    let token = String::from_utf8_lossy(token);
+    match token.parse() {
+        Ok(x) => x,
+        Err(_) => panic!(
+            "Scanner: failed to parse {:?} as {}",
+            token,
+            std::any::type_name::<T>()
+        ),
+    }
+}
+
+#[snippet("scanner")]
+/// Whitespace separated token reader buffering one line at a time.
+///
+/// Typical usage is `Scanner::new(std::io::stdin().lock())`.
+pub struct Scanner<R> {
+    reader: R,
+    line: Vec<u8>,
+    pos: usize,
+}
+
+#[snippet("scanner")]
+impl<R: std::io::BufRead> Scanner<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: vec![],
+            pos: 0,
+        }
+    }
+
+    /// Reads the next line into the buffer. `false` at EOF.
+    fn fill(&mut self) -> bool {
+        self.line.clear();
+        self.pos = 0;
+        self.reader
+            .read_until(b'\n', &mut self.line)
+            .expect("Scanner: failed to read")
+            > 0
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.line.len() && self.line[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn next_token(&mut self) -> &[u8] {
+        self.skip_whitespace();
+        while self.pos == self.line.len() {
+            assert!(self.fill(), "Scanner: unexpected EOF");
+            self.skip_whitespace();
+        }
+        let start = self.pos;
+        while self.pos < self.line.len() && !self.line[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        &self.line[start..self.pos]
+    }
+
+    /// Parses the next token. Panics at EOF or if the token is invalid.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: std::str::FromStr>(&mut self) -> T {
+        parse_token(self.next_token())
+    }
+
+    /// Next token as raw bytes. Panics at EOF.
+    pub fn next_bytes(&mut self) -> Vec<u8> {
+        self.next_token().to_vec()
+    }
+
+    pub fn next_n<T: std::str::FromStr>(&mut self, n: usize) -> Vec<T> {
+        (0..n).map(|_| self.next()).collect()
+    }
+
+    /// Rest of the current line if it has a token left, otherwise the next whole line,
+    /// without the line terminator. Panics at EOF.
+    pub fn next_line(&mut self) -> String {
+        self.skip_whitespace();
+        if self.pos == self.line.len() {
+            assert!(self.fill(), "Scanner: unexpected EOF");
+        }
+        let rest = String::from_utf8_lossy(&self.line[self.pos..]);
+        let res = rest.trim_end_matches(&['\n', '\r'][..]).to_string();
+        self.pos = self.line.len();
+        res
+    }
+}
+
+#[snippet("scanner")]
+/// Whitespace separated token reader which reads the whole input at once,
+/// faster than `Scanner` when the input is large.
+///
+/// Typical usage is `WholeInputScanner::new(std::io::stdin())`.
+pub struct WholeInputScanner {
+    input: Vec<u8>,
+    pos: usize,
+}
+
+#[snippet("scanner")]
+impl WholeInputScanner {
+    pub fn new<R: std::io::Read>(mut reader: R) -> Self {
+        let mut input = vec![];
+        reader
+            .read_to_end(&mut input)
+            .expect("Scanner: failed to read");
+        Self { input, pos: 0 }
+    }
+
+    fn next_token(&mut self) -> &[u8] {
+        while self.pos < self.input.len() && self.input[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        assert!(self.pos < self.input.len(), "Scanner: unexpected EOF");
+        let start = self.pos;
+        while self.pos < self.input.len() && !self.input[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        &self.input[start..self.pos]
+    }
+
+    /// Parses the next token. Panics at EOF or if the token is invalid.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: std::str::FromStr>(&mut self) -> T {
+        parse_token(self.next_token())
+    }
+
+    pub fn next_n<T: std::str::FromStr>(&mut self, n: usize) -> Vec<T> {
+        (0..n).map(|_| self.next()).collect()
+    }
+
+    /// Next token as raw bytes. Panics at EOF.
+    pub fn next_bytes(&mut self) -> Vec<u8> {
+        self.next_token().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_tokens() {
+        let input = "3 -5\n  2.5\t7\n\nabc 42\n\n".as_bytes();
+        let mut sc = Scanner::new(input);
+        assert_eq!(sc.next::<usize>(), 3);
+        assert_eq!(sc.next::<i64>(), -5);
+        assert_eq!(sc.next::<f64>(), 2.5);
+        assert_eq!(sc.next::<u8>(), 7);
+        assert_eq!(sc.next::<String>(), "abc");
+        assert_eq!(sc.next::<u32>(), 42);
+    }
+
+    #[test]
+    fn test_next_n() {
+        let input = "4\n1 2\n3\n4\n".as_bytes();
+        let mut sc = Scanner::new(input);
+        let n = sc.next();
+        assert_eq!(sc.next_n::<i32>(n), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_next_line() {
+        let input = "2 hello world\nfoo bar  \r\n".as_bytes();
+        let mut sc = Scanner::new(input);
+        assert_eq!(sc.next::<usize>(), 2);
+        assert_eq!(sc.next_line(), "hello world");
+        assert_eq!(sc.next_line(), "foo bar  ");
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected EOF")]
+    fn test_eof() {
+        let mut sc = Scanner::new("1 \n\n".as_bytes());
+        sc.next::<i32>();
+        sc.next::<i32>();
+    }
+
+    #[test]
+    fn test_next_bytes() {
+        let mut sc = Scanner::new("3\n#.S\n".as_bytes());
+        assert_eq!(sc.next::<usize>(), 3);
+        assert_eq!(sc.next_bytes(), b"#.S".to_vec());
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse \"1.5\" as i32")]
+    fn test_malformed_token() {
+        let mut sc = Scanner::new("1.5\n".as_bytes());
+        sc.next::<i32>();
+    }
+
+    #[test]
+    fn test_whole_input_scanner() {
+        let input = "3 -5\n  2.5\t7\n\nabc 42\n\n#.#".as_bytes();
+        let mut sc = WholeInputScanner::new(input);
+        assert_eq!(sc.next::<usize>(), 3);
+        assert_eq!(sc.next::<i64>(), -5);
+        assert_eq!(sc.next::<f64>(), 2.5);
+        assert_eq!(sc.next::<u8>(), 7);
+        assert_eq!(sc.next::<String>(), "abc");
+        assert_eq!(sc.next_n::<u32>(1), vec![42]);
+        assert_eq!(sc.next_bytes(), b"#.#".to_vec());
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected EOF")]
+    fn test_whole_input_scanner_eof() {
+        let mut sc = WholeInputScanner::new("1 \n\n".as_bytes());
+        sc.next::<i32>();
+        sc.next::<i32>();
+    }
+}
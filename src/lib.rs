@@ -1,5 +1,11 @@
 pub mod algorithms;
 pub mod data_structure;
+pub mod geometry;
+pub mod grid;
+pub mod io;
 pub mod math;
 pub mod misc;
 pub mod string;
+
+#[cfg(test)]
+mod test_util;
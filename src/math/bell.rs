@@ -0,0 +1,41 @@
+use cargo_snippet::snippet;
+
+#[snippet("bell_numbers")]
+/// Bell numbers `B(0..=n_max)` modulo `p` by the Bell triangle
+/// with time-complexity `O(n_max^2)`.
+///
+/// `B(n)` is the number of partitions of a set of size `n`.
+pub fn bell_numbers(n_max: usize, p: usize) -> Vec<usize> {
+    let mut res = vec![1 % p];
+    let mut row = vec![1 % p];
+    for _ in 0..n_max {
+        // Each row starts with the last element of the previous row.
+        let mut next = vec![*row.last().unwrap()];
+        for &x in &row {
+            let last = *next.last().unwrap();
+            next.push((last + x) % p);
+        }
+        res.push(next[0]);
+        row = next;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bell_numbers() {
+        assert_eq!(
+            bell_numbers(10, 1_000_000_007),
+            vec![1, 1, 2, 5, 15, 52, 203, 877, 4140, 21147, 115975]
+        );
+        assert_eq!(bell_numbers(0, 1_000_000_007), vec![1]);
+    }
+
+    #[test]
+    fn test_bell_numbers_mod() {
+        assert_eq!(bell_numbers(5, 7), vec![1, 1, 2, 5, 1, 3]);
+    }
+}
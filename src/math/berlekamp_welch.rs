@@ -0,0 +1,96 @@
+use crate::math::linear_equation::solve_linear_mod;
+use cargo_snippet::snippet;
+
+#[snippet("berlekamp_welch", include = "linear_equation_mod")]
+/// Recover the polynomial `f` of degree less than `k` from `n >= k + 2e` evaluations
+/// `y[i] = f(x[i])` modulo prime `p`, of which at most `e` may be wrong,
+/// by the Berlekamp-Welch algorithm with time-complexity `O(n^3)`.
+///
+/// Returns coefficients of `f` in increasing order of degree padded to length `k`,
+/// or `None` if no polynomial agrees with at least `n - e` evaluations.
+/// `x` must be distinct and `p` is expected to be less than `2^32`.
+pub fn berlekamp_welch(x: &[u64], y: &[u64], k: usize, e: usize, p: u64) -> Option<Vec<u64>> {
+    let n = x.len();
+    assert!(y.len() == n && n >= k + 2 * e);
+    // Find monic `E` of degree `e` and `Q` of degree less than `k + e`
+    // such that `Q(x_i) = y_i E(x_i)` for every `i`.
+    let mut a = vec![vec![0; k + 2 * e]; n];
+    let mut b = vec![0; n];
+    for i in 0..n {
+        let (xi, yi) = (x[i] % p, y[i] % p);
+        let mut pw = vec![1; k + e + 1];
+        for j in 1..=k + e {
+            pw[j] = pw[j - 1] * xi % p;
+        }
+        a[i][..k + e].copy_from_slice(&pw[..k + e]);
+        for j in 0..e {
+            a[i][k + e + j] = (p - yi) * pw[j] % p;
+        }
+        b[i] = yi * pw[e] % p;
+    }
+    let sol = solve_linear_mod(&a, &b, p)?;
+    let mut q = sol[..k + e].to_vec();
+    let mut err = sol[k + e..].to_vec();
+    err.push(1);
+
+    // Divide `Q` by monic `E`.
+    let mut f = vec![0; k];
+    for d in (0..k).rev() {
+        let c = q[d + e];
+        f[d] = c;
+        for j in 0..=e {
+            q[d + j] = (q[d + j] + p - c * err[j] % p) % p;
+        }
+    }
+    if q.iter().any(|&c| c != 0) {
+        return None;
+    }
+    let eval = |t: u64| f.iter().rev().fold(0, |acc, &c| (acc * (t % p) + c) % p);
+    let agree = (0..n).filter(|&i| eval(x[i]) == y[i] % p).count();
+    if agree + e >= n {
+        Some(f)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u64 = 998_244_353;
+
+    fn eval(f: &[u64], t: u64) -> u64 {
+        f.iter().rev().fold(0, |acc, &c| (acc * t + c) % P)
+    }
+
+    #[test]
+    fn test_recover_with_errors() {
+        let f = vec![5, 3, 7];
+        let x = (1..=8).collect::<Vec<u64>>();
+        let mut y = x.iter().map(|&t| eval(&f, t)).collect::<Vec<_>>();
+        y[1] = (y[1] + 10) % P;
+        y[6] = 12345;
+        assert_eq!(berlekamp_welch(&x, &y, 3, 2, P), Some(f));
+    }
+
+    #[test]
+    fn test_too_many_errors() {
+        let f = vec![5, 3, 7];
+        let x = (1..=7).collect::<Vec<u64>>();
+        let mut y = x.iter().map(|&t| eval(&f, t)).collect::<Vec<_>>();
+        for (i, v) in [(0, 1), (2, 100), (3, 42), (5, 7)].iter() {
+            y[*i] = *v;
+        }
+        assert_eq!(berlekamp_welch(&x, &y, 3, 2, P), None);
+    }
+
+    #[test]
+    fn test_without_errors_is_interpolation() {
+        let f = vec![1, 0, 2, 9];
+        let x = vec![3, 10, 4, 7];
+        let y = x.iter().map(|&t| eval(&f, t)).collect::<Vec<_>>();
+        assert_eq!(berlekamp_welch(&x, &y, 4, 0, P), Some(f));
+        assert_eq!(berlekamp_welch(&x, &y, 3, 0, P), None);
+    }
+}
@@ -0,0 +1,148 @@
+use cargo_snippet::snippet;
+
+#[snippet("big_uint")]
+/// Arbitrary precision unsigned integer with limbs of base `10^9` in little endian.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+#[snippet("big_uint")]
+impl BigUint {
+    const BASE: u64 = 1_000_000_000;
+
+    pub fn from_u64(mut x: u64) -> Self {
+        let mut limbs = vec![];
+        while x > 0 {
+            limbs.push((x % Self::BASE) as u32);
+            x /= Self::BASE;
+        }
+        Self { limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// `n!` with time-complexity `O(n^2 log n)`.
+    pub fn factorial(n: u64) -> Self {
+        (2..=n).fold(Self::from_u64(1), |acc, i| acc * Self::from_u64(i))
+    }
+}
+
+#[snippet("big_uint")]
+impl Default for BigUint {
+    fn default() -> Self {
+        Self::from_u64(0)
+    }
+}
+
+#[snippet("big_uint")]
+impl std::fmt::Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.limbs.split_last() {
+            None => write!(f, "0"),
+            Some((top, rest)) => {
+                write!(f, "{}", top)?;
+                for x in rest.iter().rev() {
+                    write!(f, "{:09}", x)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[snippet("big_uint")]
+impl std::ops::Add for BigUint {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let n = self.limbs.len().max(rhs.limbs.len());
+        let mut limbs = Vec::with_capacity(n + 1);
+        let mut carry = 0;
+        for i in 0..n {
+            let a = *self.limbs.get(i).unwrap_or(&0) as u64;
+            let b = *rhs.limbs.get(i).unwrap_or(&0) as u64;
+            let s = a + b + carry;
+            limbs.push((s % Self::BASE) as u32);
+            carry = s / Self::BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+        Self { limbs }
+    }
+}
+
+#[snippet("big_uint")]
+impl std::ops::Mul for BigUint {
+    type Output = Self;
+    /// Schoolbook multiplication with time-complexity `O(nm)`.
+    fn mul(self, rhs: Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::default();
+        }
+        let mut acc = vec![0_u64; self.limbs.len() + rhs.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in rhs.limbs.iter().enumerate() {
+                let t = acc[i + j] + a as u64 * b as u64 + carry;
+                acc[i + j] = t % Self::BASE;
+                carry = t / Self::BASE;
+            }
+            acc[i + rhs.limbs.len()] += carry;
+        }
+        while acc.last() == Some(&0) {
+            acc.pop();
+        }
+        Self {
+            limbs: acc.into_iter().map(|x| x as u32).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test_factorial() {
+        assert_eq!(BigUint::factorial(0).to_string(), "1");
+        assert_eq!(BigUint::factorial(20).to_string(), "2432902008176640000");
+        assert_eq!(
+            BigUint::factorial(50).to_string(),
+            "30414093201713378043612608166064768844377641568960512000000000000"
+        );
+    }
+
+    #[test]
+    fn test_to_string_pads_inner_limbs() {
+        assert_eq!(BigUint::from_u64(0).to_string(), "0");
+        assert_eq!(BigUint::from_u64(1_000_000_007).to_string(), "1000000007");
+        assert_eq!(BigUint::from_u64(1_000_000_000).to_string(), "1000000000");
+        assert_eq!(BigUint::from_u64(!0).to_string(), "18446744073709551615");
+    }
+
+    #[test]
+    fn test_add_and_mul_match_u128() {
+        let mut rng = XorShift::new();
+        let mut next = || {
+            let x = rng.next_u64();
+            x >> (x % 64)
+        };
+        for _ in 0..200 {
+            let (a, b) = (next(), next());
+            let (ba, bb) = (BigUint::from_u64(a), BigUint::from_u64(b));
+            assert_eq!(
+                (ba.clone() + bb.clone()).to_string(),
+                (a as u128 + b as u128).to_string()
+            );
+            assert_eq!((ba * bb).to_string(), (a as u128 * b as u128).to_string());
+        }
+        assert_eq!(
+            BigUint::from_u64(0) * BigUint::from_u64(5),
+            BigUint::default()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::math::pow::pow_monoid;
+use cargo_snippet::snippet;
+
+#[snippet("burnside", include = "pow_monoid")]
+/// Inverse of `a` modulo prime `p` by Fermat's little theorem.
+pub fn mod_inverse_prime(a: usize, p: usize) -> usize {
+    assert!(a % p != 0);
+    pow_monoid(a % p, (p - 2) as u64, |x, y| x * y % p, 1)
+}
+
+#[snippet("burnside", include = "pow_monoid")]
+/// Number of colorings of `n_positions` positions with `n_colors` colors modulo prime `modulus`
+/// up to the group whose elements are the permutations of positions `group_actions`,
+/// by Burnside's lemma with time-complexity `O(|G| n_positions)`.
+///
+/// Colorings fixed by a permutation are constant on its cycles,
+/// and there are `n_colors^cycles` of them.
+/// `modulus` must be larger than the group size.
+pub fn burnside_count<F: Fn(usize) -> usize>(
+    group_actions: &[F],
+    n_colors: usize,
+    n_positions: usize,
+    modulus: usize,
+) -> usize {
+    let mut sum = 0;
+    for action in group_actions {
+        let mut visited = vec![false; n_positions];
+        let mut cycles = 0;
+        for i in 0..n_positions {
+            if visited[i] {
+                continue;
+            }
+            cycles += 1;
+            let mut j = i;
+            while !visited[j] {
+                visited[j] = true;
+                j = action(j);
+            }
+        }
+        sum = (sum + pow_monoid(n_colors % modulus, cycles, |x, y| x * y % modulus, 1)) % modulus;
+    }
+    sum * mod_inverse_prime(group_actions.len(), modulus) % modulus
+}
+
+#[snippet("burnside", include = "pow_monoid")]
+/// Number of necklaces of `n_beads` beads with `n_colors` colors modulo prime `modulus`,
+/// i.e. colorings up to rotation.
+pub fn necklace_count(n_beads: usize, n_colors: usize, modulus: usize) -> usize {
+    assert!(n_beads > 0);
+    let gcd = |mut a: usize, mut b: usize| {
+        while b > 0 {
+            let r = a % b;
+            a = b;
+            b = r;
+        }
+        a
+    };
+    // Rotation by `k` consists of `gcd(k, n)` cycles.
+    let sum = (0..n_beads).fold(0, |acc, k| {
+        let cycles = gcd(k, n_beads) as u64;
+        (acc + pow_monoid(n_colors % modulus, cycles, |x, y| x * y % modulus, 1)) % modulus
+    });
+    sum * mod_inverse_prime(n_beads, modulus) % modulus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD: usize = 1_000_000_007;
+
+    #[test]
+    fn test_necklace_count() {
+        assert_eq!(necklace_count(4, 2, MOD), 6);
+        assert_eq!(necklace_count(1, 5, MOD), 5);
+        assert_eq!(necklace_count(6, 3, MOD), 130);
+    }
+
+    #[test]
+    fn test_necklace_matches_burnside() {
+        for n in 1..=8 {
+            let rotations = (0..n).map(|k| move |i| (i + k) % n).collect::<Vec<_>>();
+            for c in 1..=4 {
+                assert_eq!(
+                    burnside_count(&rotations, c, n, MOD),
+                    necklace_count(n, c, MOD)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bracelet() {
+        let n = 3;
+        let mut actions: Vec<Box<dyn Fn(usize) -> usize>> = vec![];
+        for k in 0..n {
+            actions.push(Box::new(move |i| (i + k) % n));
+            actions.push(Box::new(move |i| (k + n - i) % n));
+        }
+        assert_eq!(burnside_count(&actions, 3, n, MOD), 10);
+    }
+
+    #[test]
+    fn test_mod_inverse_prime() {
+        assert_eq!(mod_inverse_prime(3, 7), 5);
+        assert_eq!(mod_inverse_prime(2, MOD) * 2 % MOD, 1);
+    }
+}
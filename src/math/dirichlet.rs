@@ -0,0 +1,76 @@
+use crate::math::linear_sieve::LinearSieve;
+use cargo_snippet::snippet;
+
+#[snippet("dirichlet", include = "linear_sieve")]
+/// Transform `f` into `g` where `g[n]` is the sum of `f[d]` over divisors `d` of `n`,
+/// with time-complexity `O(n log log n)`. `f[0]` is left untouched.
+///
+/// `sieve` must cover `f.len() - 1`.
+pub fn dirichlet_prefix_sum(f: &mut [i64], sieve: &LinearSieve) {
+    let n = f.len().saturating_sub(1);
+    for &p in sieve.primes.iter().take_while(|&&p| p <= n) {
+        for i in 1..=n / p {
+            f[i * p] += f[i];
+        }
+    }
+}
+
+#[snippet("dirichlet", include = "linear_sieve")]
+/// Inverse of `dirichlet_prefix_sum`, i.e. Möbius inversion,
+/// with time-complexity `O(n log log n)`. `f[0]` is left untouched.
+///
+/// `sieve` must cover `f.len() - 1`.
+pub fn dirichlet_inverse(f: &mut [i64], sieve: &LinearSieve) {
+    let n = f.len().saturating_sub(1);
+    for &p in sieve.primes.iter().take_while(|&&p| p <= n) {
+        for i in (1..=n / p).rev() {
+            f[i * p] -= f[i];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::ratio::gcd;
+
+    const N: usize = 1_000;
+
+    #[test]
+    fn test_divisor_count() {
+        let sieve = LinearSieve::new(N);
+        let mut f = vec![1; N + 1];
+        dirichlet_prefix_sum(&mut f, &sieve);
+        for (n, &cnt) in f.iter().enumerate().skip(1) {
+            assert_eq!(cnt, (1..=n).filter(|d| n % d == 0).count() as i64);
+        }
+    }
+
+    #[test]
+    fn test_totient_sums_to_identity() {
+        let sieve = LinearSieve::new(N);
+        let mut f = (0..=N)
+            .map(|n| (1..=n).filter(|&k| gcd(n, k) == 1).count() as i64)
+            .collect::<Vec<_>>();
+        dirichlet_prefix_sum(&mut f, &sieve);
+        assert_eq!(f[1..], (1..=N as i64).collect::<Vec<_>>()[..]);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let sieve = LinearSieve::new(N);
+        let original = (0..=N as i64).map(|n| n * n % 17 - 8).collect::<Vec<_>>();
+        let mut f = original.clone();
+        dirichlet_prefix_sum(&mut f, &sieve);
+        dirichlet_inverse(&mut f, &sieve);
+        assert_eq!(f, original);
+    }
+
+    #[test]
+    fn test_inverse_of_identity_is_totient() {
+        let sieve = LinearSieve::new(100);
+        let mut f = (0..=100).collect::<Vec<_>>();
+        dirichlet_inverse(&mut f, &sieve);
+        assert_eq!(f[1..13], [1, 1, 2, 2, 4, 2, 6, 4, 6, 4, 10, 4]);
+    }
+}
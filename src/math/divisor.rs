@@ -1,3 +1,4 @@
+use crate::math::eratosthenes::Eratosthenes;
 use cargo_snippet::snippet;
 
 #[snippet("divisor")]
@@ -19,6 +20,60 @@ pub fn enumerate_divisors(n: usize) -> Vec<usize> {
     front
 }
 
+#[snippet("largest_divisor_at_most")]
+/// Largest divisor of `n` not exceeding `k` with time-complexity `O(sqrt(n))`.
+///
+/// Returns `0` if `k == 0` as no divisor qualifies, and `k` if `n == 0`.
+pub fn largest_divisor_at_most(n: usize, k: usize) -> usize {
+    if k == 0 || n == 0 {
+        return k;
+    }
+    let mut best = 1;
+    for i in (1..).take_while(|&i| i * i <= n) {
+        if n % i != 0 {
+            continue;
+        }
+        if i <= k {
+            best = best.max(i);
+        }
+        if n / i <= k {
+            best = best.max(n / i);
+        }
+    }
+    best
+}
+
+#[snippet("factorize_with_exponents", include = "eratosthenes")]
+/// Prime factorization of `n` as `(prime, exponent)` pairs in increasing order of prime.
+pub fn factorize_with_exponents(n: usize, sieve: &Eratosthenes) -> Vec<(usize, u32)> {
+    let mut res: Vec<(usize, u32)> = vec![];
+    for p in sieve.factorize(n) {
+        match res.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => res.push((p, 1)),
+        }
+    }
+    res
+}
+
+#[snippet("divisors_from_factorization")]
+/// All divisors in increasing order of the number whose prime factorization is `factors`.
+pub fn divisors_from_factorization(factors: &[(usize, u32)]) -> Vec<usize> {
+    let mut res = vec![1];
+    for &(p, e) in factors {
+        let len = res.len();
+        let mut pk = 1;
+        for _ in 0..e {
+            pk *= p;
+            for i in 0..len {
+                res.push(res[i] * pk);
+            }
+        }
+    }
+    res.sort_unstable();
+    res
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +84,43 @@ mod tests {
         assert_eq!(enumerate_divisors(25), [1, 5, 25]);
         assert_eq!(enumerate_divisors(17), [1, 17]);
     }
+
+    #[test]
+    fn test_largest_divisor_at_most() {
+        assert_eq!(largest_divisor_at_most(12, 5), 4);
+        assert_eq!(largest_divisor_at_most(12, 12), 12);
+        assert_eq!(largest_divisor_at_most(12, 100), 12);
+        assert_eq!(largest_divisor_at_most(12, 1), 1);
+        assert_eq!(largest_divisor_at_most(17, 16), 1);
+        assert_eq!(largest_divisor_at_most(12, 0), 0);
+        assert_eq!(largest_divisor_at_most(0, 7), 7);
+    }
+
+    #[test]
+    fn test_largest_divisor_at_most_matches_enumeration() {
+        for n in 1..200 {
+            let divisors = enumerate_divisors(n);
+            for k in 1..=n + 1 {
+                let expected = *divisors.iter().filter(|&&d| d <= k).max().unwrap();
+                assert_eq!(largest_divisor_at_most(n, k), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_factorize_with_exponents() {
+        let e = Eratosthenes::new(1_000);
+        assert_eq!(factorize_with_exponents(360, &e), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize_with_exponents(997, &e), [(997, 1)]);
+        assert_eq!(factorize_with_exponents(1, &e), []);
+    }
+
+    #[test]
+    fn test_divisors_from_factorization() {
+        let e = Eratosthenes::new(1_000);
+        for n in 1..=1_000 {
+            let factors = factorize_with_exponents(n, &e);
+            assert_eq!(divisors_from_factorization(&factors), enumerate_divisors(n));
+        }
+    }
 }
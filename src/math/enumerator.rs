@@ -59,7 +59,12 @@ impl Enumerator {
         self.fact[n] * self.finv[n - k] % self.p
     }
 
+    /// Number of multisets of size `k` from `n` kinds, i.e. `C(n + k - 1, k)`.
+    /// There is exactly one way to choose nothing, even from no kinds.
     pub fn choose_with_duplicates(&self, n: usize, k: usize) -> usize {
+        if n == 0 {
+            return if k == 0 { 1 } else { 0 };
+        }
         self.choose(n + k - 1, k)
     }
 }
@@ -107,5 +112,13 @@ mod tests {
         assert_eq!(e.choose_with_duplicates(3, 0), 1);
         assert_eq!(e.choose_with_duplicates(3, 1), 3);
         assert_eq!(e.choose_with_duplicates(3, 4), 15);
+        assert_eq!(e.choose_with_duplicates(1, 5), 1);
+    }
+
+    #[test]
+    fn test_choose_with_duplicates_from_nothing() {
+        let e = Enumerator::new(100, 1_000_000_007);
+        assert_eq!(e.choose_with_duplicates(0, 0), 1);
+        assert_eq!(e.choose_with_duplicates(0, 3), 0);
     }
 }
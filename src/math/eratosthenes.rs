@@ -51,6 +51,45 @@ impl Eratosthenes {
     }
 }
 
+#[snippet("bit_sieve")]
+/// Eratosthenes sieve only for primarity test,
+/// storing a bit per odd number to use about `n_max / 16` bytes.
+pub struct BitSieve {
+    n_max: usize,
+    composite: Vec<u64>,
+}
+
+#[snippet("bit_sieve")]
+impl BitSieve {
+    /// Constructs a new BitSieve struct with time-complexity `O(n log log n)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_max`: Max number to check primarity (inclusive).
+    pub fn new(n_max: usize) -> Self {
+        // Bit `k` stands for the odd number `2k + 1`.
+        let mut composite = vec![0_u64; (n_max >> 1) / 64 + 1];
+        for i in (3..).step_by(2).take_while(|&x| x * x <= n_max) {
+            if composite[(i >> 1) >> 6] >> ((i >> 1) & 63) & 1 == 1 {
+                continue;
+            }
+            for j in (i * i..).step_by(2 * i).take_while(|&x| x <= n_max) {
+                composite[(j >> 1) >> 6] |= 1 << ((j >> 1) & 63);
+            }
+        }
+        Self { n_max, composite }
+    }
+
+    /// Tests if `n` is a prime number.
+    pub fn is_prime(&self, n: usize) -> bool {
+        assert!(n <= self.n_max);
+        if n < 3 || n & 1 == 0 {
+            return n == 2;
+        }
+        self.composite[(n >> 1) >> 6] >> ((n >> 1) & 63) & 1 == 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,4 +120,34 @@ mod tests {
         assert_eq!(e.factorize(120), vec![2, 2, 2, 3, 5]);
         assert_eq!(e.factorize(836427), vec![3, 278809]);
     }
+
+    #[test]
+    fn test_bit_sieve_agrees_with_eratosthenes() {
+        let n_max = 100_000;
+        let e = Eratosthenes::new(n_max);
+        let b = BitSieve::new(n_max);
+        for n in 0..=n_max {
+            assert_eq!(b.is_prime(n), e.is_prime(n), "n = {}", n);
+        }
+        for &n_max in [0, 1, 2, 3, 63, 64, 127, 128, 129].iter() {
+            let e = Eratosthenes::new(n_max);
+            let b = BitSieve::new(n_max);
+            assert!((0..=n_max).all(|n| b.is_prime(n) == e.is_prime(n)));
+        }
+    }
+
+    #[test]
+    fn test_bit_sieve_large() {
+        let b = BitSieve::new(10_000_000);
+        assert_eq!((0..=10_000_000).filter(|&n| b.is_prime(n)).count(), 664_579);
+        assert!(b.is_prime(9_999_991));
+        assert!(!b.is_prime(10_000_000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bit_sieve_out_of_bounds() {
+        let b = BitSieve::new(10);
+        b.is_prime(11);
+    }
 }
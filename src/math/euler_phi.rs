@@ -0,0 +1,58 @@
+use cargo_snippet::snippet;
+
+#[snippet("euler_phi_single")]
+/// Euler's totient function `φ(n)` by trial division with time-complexity `O(sqrt n)`.
+///
+/// `φ(n) = n Π (1 - 1/p)` over distinct prime factors `p` of `n`.
+pub fn euler_phi_single(n: u64) -> u64 {
+    let mut res = n;
+    let mut m = n;
+    let mut d = 2;
+    while d <= m / d {
+        if m % d == 0 {
+            res = res / d * (d - 1);
+            while m % d == 0 {
+                m /= d;
+            }
+        }
+        d += 1;
+    }
+    // Remaining factor larger than `sqrt m` is a prime.
+    if m > 1 {
+        res = res / m * (m - 1);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_euler_phi_single() {
+        assert_eq!(euler_phi_single(1), 1);
+        assert_eq!(euler_phi_single(12), 4);
+        assert_eq!(euler_phi_single(97), 96);
+        assert_eq!(euler_phi_single(3_u64.pow(5)), 3_u64.pow(4) * 2);
+        assert_eq!(euler_phi_single(1_000_000_007), 1_000_000_006);
+        assert_eq!(euler_phi_single(1 << 32), 1 << 31);
+        // 2 * 999999937 has a large prime factor.
+        assert_eq!(euler_phi_single(2 * 999_999_937), 999_999_936);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let gcd = |mut a: u64, mut b: u64| {
+            while b > 0 {
+                let r = a % b;
+                a = b;
+                b = r;
+            }
+            a
+        };
+        for n in 1..=300 {
+            let expected = (1..=n).filter(|&k| gcd(k, n) == 1).count() as u64;
+            assert_eq!(euler_phi_single(n), expected);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::math::pow::pow_monoid;
+use cargo_snippet::snippet;
+
+#[snippet("lagrange_interpolate", include = "pow_monoid")]
+/// Value at `query` of the unique polynomial of degree less than `n` through `(xs[i], ys[i])`
+/// modulo prime `p` by barycentric weights with time-complexity `O(n^2)`.
+///
+/// `xs` must be distinct modulo `p`, and `p` is expected to be less than `2^32`.
+pub fn lagrange_interpolate(xs: &[u64], ys: &[u64], query: u64, p: u64) -> u64 {
+    let n = xs.len();
+    assert_eq!(n, ys.len());
+    let inv = |a: u64| pow_monoid(a, p - 2, |x, y| x * y % p, 1);
+    let q = query % p;
+    if let Some(i) = xs.iter().position(|&x| x % p == q) {
+        return ys[i] % p;
+    }
+    // `l = prod (q - x_j)`, `w_i = 1 / prod_{j != i} (x_i - x_j)`
+    let l = xs.iter().fold(1, |acc, &x| acc * ((q + p - x % p) % p) % p);
+    let mut res = 0;
+    for i in 0..n {
+        let mut denom = (q + p - xs[i] % p) % p;
+        for j in 0..n {
+            if i != j {
+                denom = denom * ((xs[i] % p + p - xs[j] % p) % p) % p;
+            }
+        }
+        res = (res + ys[i] % p * inv(denom)) % p;
+    }
+    res * l % p
+}
+
+#[snippet("lagrange_interpolate", include = "pow_monoid")]
+/// Same as `lagrange_interpolate` with `xs = [0, 1, ..., n - 1]`
+/// with time-complexity `O(n + log p)` by prefix and suffix products.
+///
+/// `p` must be larger than `n`.
+pub fn lagrange_interpolate_consecutive(y0: &[u64], query: u64, p: u64) -> u64 {
+    let n = y0.len();
+    assert!((n as u64) < p);
+    let q = query % p;
+    if q < n as u64 {
+        return y0[q as usize] % p;
+    }
+    // `pre[i] = prod_{j < i} (q - j)`, `suf[i] = prod_{j >= i} (q - j)`
+    let mut pre = vec![1; n + 1];
+    let mut suf = vec![1; n + 1];
+    for i in 0..n {
+        pre[i + 1] = pre[i] * ((q + p - i as u64) % p) % p;
+    }
+    for i in (0..n).rev() {
+        suf[i] = suf[i + 1] * ((q + p - i as u64) % p) % p;
+    }
+    let mut finv = vec![1; n + 1];
+    let fact = (1..=n as u64).fold(1, |acc, i| acc * i % p);
+    finv[n] = pow_monoid(fact, p - 2, |x, y| x * y % p, 1);
+    for i in (1..=n).rev() {
+        finv[i - 1] = finv[i] * i as u64 % p;
+    }
+    let mut res = 0;
+    for i in 0..n {
+        // `prod_{j != i} (i - j) = i! (n - 1 - i)! (-1)^(n - 1 - i)`
+        let mut term = y0[i] % p * pre[i] % p * suf[i + 1] % p * finv[i] % p * finv[n - 1 - i] % p;
+        if (n - 1 - i) & 1 == 1 {
+            term = (p - term) % p;
+        }
+        res = (res + term) % p;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u64 = 998_244_353;
+
+    #[test]
+    fn test_lagrange_interpolate() {
+        assert_eq!(lagrange_interpolate(&[0, 1, 2], &[0, 1, 4], 3, P), 9);
+        assert_eq!(lagrange_interpolate(&[5, 1, 3], &[25, 1, 9], 2, P), 4);
+        assert_eq!(lagrange_interpolate(&[5, 1, 3], &[25, 1, 9], 1, P), 1);
+        // x^3 - x at large x
+        let xs = [2, 7, 11, 20];
+        let f = |x: u64| (x * x % P * x % P + P - x) % P;
+        let ys = xs.iter().map(|&x| f(x)).collect::<Vec<_>>();
+        assert_eq!(lagrange_interpolate(&xs, &ys, 1_000_000, P), f(1_000_000));
+    }
+
+    #[test]
+    fn test_lagrange_interpolate_consecutive() {
+        assert_eq!(lagrange_interpolate_consecutive(&[0, 1, 4], 3, P), 9);
+        assert_eq!(lagrange_interpolate_consecutive(&[0, 1, 4], 1, P), 1);
+        // Sum of squares `0^2 + ... + x^2` is a polynomial of degree 3.
+        let y0 = [0, 1, 5, 14];
+        let n = 1_000_u64;
+        assert_eq!(
+            lagrange_interpolate_consecutive(&y0, n, P),
+            n * (n + 1) * (2 * n + 1) / 6 % P
+        );
+    }
+
+    #[test]
+    fn test_consecutive_matches_general() {
+        // Fibonacci-like sequence seen as values of the polynomial through its first terms
+        let y0 = [1, 1, 2, 3, 5, 8, 13, 21];
+        let xs = (0..y0.len() as u64).collect::<Vec<_>>();
+        for query in 0..50 {
+            assert_eq!(
+                lagrange_interpolate_consecutive(&y0, query, P),
+                lagrange_interpolate(&xs, &y0, query, P)
+            );
+        }
+    }
+}
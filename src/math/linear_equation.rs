@@ -0,0 +1,80 @@
+use crate::math::pow::pow_monoid;
+use cargo_snippet::snippet;
+
+#[snippet("linear_equation_mod", include = "pow_monoid")]
+/// A solution of linear equations `a x = b` modulo prime `p` by Gaussian elimination
+/// with time-complexity `O(nm min(n, m))` for `n` equations of `m` unknowns.
+///
+/// Free variables are set to `0`. `None` if there is no solution.
+/// `p` is expected to be less than `2^32`.
+pub fn solve_linear_mod(a: &[Vec<u64>], b: &[u64], p: u64) -> Option<Vec<u64>> {
+    let n = a.len();
+    let m = if n == 0 { 0 } else { a[0].len() };
+    assert!(b.len() == n && a.iter().all(|row| row.len() == m));
+    // Augmented matrix
+    let mut mat = a
+        .iter()
+        .zip(b)
+        .map(|(row, &bi)| {
+            let mut row = row.iter().map(|&x| x % p).collect::<Vec<_>>();
+            row.push(bi % p);
+            row
+        })
+        .collect::<Vec<_>>();
+    let mut pivots = vec![];
+    let mut rank = 0;
+    for col in 0..m {
+        let pivot = match (rank..n).find(|&i| mat[i][col] != 0) {
+            Some(i) => i,
+            None => continue,
+        };
+        mat.swap(rank, pivot);
+        let inv = pow_monoid(mat[rank][col], p - 2, |x, y| x * y % p, 1);
+        for x in mat[rank].iter_mut() {
+            *x = *x * inv % p;
+        }
+        let pivot_row = mat[rank].clone();
+        for (i, row) in mat.iter_mut().enumerate() {
+            if i != rank && row[col] != 0 {
+                let f = row[col];
+                for (x, &y) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *x = (*x + p - f * y % p) % p;
+                }
+            }
+        }
+        pivots.push(col);
+        rank += 1;
+    }
+    if mat[rank..].iter().any(|row| row[m] != 0) {
+        return None;
+    }
+    let mut x = vec![0; m];
+    for (i, &col) in pivots.iter().enumerate() {
+        x[col] = mat[i][m];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u64 = 1_000_000_007;
+
+    #[test]
+    fn test_unique_solution() {
+        // x + 2y = 5, 3x + 4y = 6
+        let a = vec![vec![1, 2], vec![3, 4]];
+        let x = solve_linear_mod(&a, &[5, 6], P).unwrap();
+        assert_eq!((x[0] + 2 * x[1]) % P, 5);
+        assert_eq!((3 * x[0] + 4 * x[1]) % P, 6);
+        assert_eq!(x[0], P - 4);
+    }
+
+    #[test]
+    fn test_underdetermined_and_inconsistent() {
+        let a = vec![vec![1, 1, 0], vec![2, 2, 0]];
+        assert_eq!(solve_linear_mod(&a, &[3, 6], P), Some(vec![3, 0, 0]));
+        assert_eq!(solve_linear_mod(&a, &[3, 5], P), None);
+    }
+}
@@ -59,6 +59,38 @@ impl LinearSieve {
         res.push(self.lpf[i]);
         res
     }
+
+    /// Returns pairs of a prime factor of `n` and its exponent
+    /// in increasing order of the prime with time-complexity `O(log n)`
+    pub fn factorize_pairs(&self, n: usize) -> Vec<(usize, u32)> {
+        let mut res: Vec<(usize, u32)> = vec![];
+        let mut i = n;
+        while i > 1 {
+            let p = self.lpf[i];
+            match res.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => res.push((p, 1)),
+            }
+            i /= p;
+        }
+        res
+    }
+
+    /// Product of distinct prime factors of `n`, i.e. the largest squarefree divisor,
+    /// with time-complexity `O(log n)`. `radical(1) == 1`.
+    pub fn radical(&self, n: usize) -> usize {
+        assert!(n > 0);
+        let mut res = 1;
+        let mut i = n;
+        while i > 1 {
+            let p = self.lpf[i];
+            res *= p;
+            while i % p == 0 {
+                i /= p;
+            }
+        }
+        res
+    }
 }
 
 #[cfg(test)]
@@ -92,6 +124,25 @@ mod tests {
         assert_eq!(l.factorize(836427), vec![3, 278809]);
     }
 
+    #[test]
+    fn test_factorize_pairs() {
+        let l = LinearSieve::new(1_000);
+        assert_eq!(l.factorize_pairs(1), vec![]);
+        assert_eq!(l.factorize_pairs(120), vec![(2, 3), (3, 1), (5, 1)]);
+        assert_eq!(l.factorize_pairs(997), vec![(997, 1)]);
+        assert_eq!(l.factorize_pairs(1_000), vec![(2, 3), (5, 3)]);
+    }
+
+    #[test]
+    fn test_radical() {
+        let l = LinearSieve::new(1_000);
+        assert_eq!(l.radical(12), 6);
+        assert_eq!(l.radical(17), 17);
+        assert_eq!(l.radical(1), 1);
+        assert_eq!(l.radical(1_000), 10);
+        assert_eq!(l.radical(210), 210);
+    }
+
     #[test]
     fn test_list_primes() {
         let l = LinearSieve::new(29);
@@ -1,5 +1,18 @@
+pub mod bell;
+pub mod berlekamp_welch;
+pub mod bigint;
+pub mod burnside;
+pub mod dirichlet;
 pub mod divisor;
 pub mod enumerator;
 pub mod eratosthenes;
+pub mod euler_phi;
+pub mod lagrange;
+pub mod linear_equation;
 pub mod linear_sieve;
+pub mod mod_int;
+pub mod polynomial;
+pub mod pow;
+pub mod prime_count;
 pub mod ratio;
+pub mod xor_basis;
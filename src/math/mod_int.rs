@@ -0,0 +1,598 @@
+use cargo_snippet::snippet;
+
+#[snippet("mod_int")]
+/// Modulus of `ModInt` fixed at compile time, which must be a prime less than `2^32`.
+pub trait Modulus: Copy + Eq {
+    const M: u64;
+}
+
+#[snippet("mod_int")]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct Mod998244353;
+
+#[snippet("mod_int")]
+impl Modulus for Mod998244353 {
+    const M: u64 = 998_244_353;
+}
+
+#[snippet("mod_int")]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct Mod1000000007;
+
+#[snippet("mod_int")]
+impl Modulus for Mod1000000007 {
+    const M: u64 = 1_000_000_007;
+}
+
+#[snippet("mod_int")]
+/// Residue modulo prime `M::M` fixed at compile time.
+///
+/// Other moduli are declared by a unit struct implementing `Modulus`.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct ModInt<M> {
+    val: u64,
+    modulus: std::marker::PhantomData<M>,
+}
+
+#[snippet("mod_int")]
+pub type ModInt998244353 = ModInt<Mod998244353>;
+
+#[snippet("mod_int")]
+pub type ModInt1000000007 = ModInt<Mod1000000007>;
+
+#[snippet("mod_int")]
+impl<M: Modulus> ModInt<M> {
+    pub fn new(val: u64) -> Self {
+        ModInt {
+            val: val % M::M,
+            modulus: std::marker::PhantomData,
+        }
+    }
+
+    pub fn val(&self) -> u64 {
+        self.val
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = *self;
+        let mut res = ModInt::new(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// Multiplicative inverse by Fermat's little theorem. Panics if `val` is `0`.
+    pub fn inv(&self) -> Self {
+        assert!(self.val != 0, "ModInt: 0 is not invertible");
+        self.pow(M::M - 2)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> Default for ModInt<M> {
+    fn default() -> Self {
+        ModInt::new(0)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> From<u64> for ModInt<M> {
+    fn from(val: u64) -> Self {
+        ModInt::new(val)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> From<u32> for ModInt<M> {
+    fn from(val: u32) -> Self {
+        ModInt::new(val as u64)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> From<i64> for ModInt<M> {
+    fn from(val: i64) -> Self {
+        let m = M::M as i64;
+        ModInt::new(((val % m + m) % m) as u64)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::ops::Neg for ModInt<M> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        ModInt::new(M::M - self.val)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::ops::Add for ModInt<M> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let val = self.val + rhs.val;
+        ModInt {
+            val: if val >= M::M { val - M::M } else { val },
+            modulus: std::marker::PhantomData,
+        }
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::ops::Sub for ModInt<M> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::ops::Mul for ModInt<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        ModInt {
+            val: self.val * rhs.val % M::M,
+            modulus: std::marker::PhantomData,
+        }
+    }
+}
+
+#[snippet("mod_int")]
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<M: Modulus> std::ops::Div for ModInt<M> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::ops::AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::ops::SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::ops::MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::ops::DivAssign for ModInt<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::iter::Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt::new(0), |acc, x| acc + x)
+    }
+}
+
+#[snippet("mod_int")]
+impl<M: Modulus> std::iter::Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt::new(1), |acc, x| acc * x)
+    }
+}
+
+#[snippet("dyn_mod_int")]
+thread_local! {
+    #[allow(clippy::missing_const_for_thread_local)]
+    static DYN_MODULUS: std::cell::Cell<u64> = std::cell::Cell::new(0);
+}
+
+#[snippet("dyn_mod_int")]
+/// Residue modulo `modulus` given at runtime.
+///
+/// Operands of binary operators must share the same modulus.
+/// `From` conversions use the modulus registered by `DynModInt::set_modulus`.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct DynModInt {
+    val: u64,
+    modulus: u64,
+}
+
+#[snippet("dyn_mod_int")]
+impl DynModInt {
+    pub fn new(val: u64, m: u64) -> Self {
+        assert!(
+            m > 0 && m <= 1 << 62,
+            "DynModInt: modulus must be in [1, 2^62]"
+        );
+        DynModInt {
+            val: val % m,
+            modulus: m,
+        }
+    }
+
+    /// Registers `m` as the modulus of `From` conversions, `Sum` and `Product` in this thread.
+    pub fn set_modulus(m: u64) {
+        assert!(
+            m > 0 && m <= 1 << 62,
+            "DynModInt: modulus must be in [1, 2^62]"
+        );
+        DYN_MODULUS.with(|c| c.set(m));
+    }
+
+    /// Modulus registered by `set_modulus`. Panics if none is registered.
+    pub fn global_modulus() -> u64 {
+        let m = DYN_MODULUS.with(|c| c.get());
+        assert!(m > 0, "DynModInt: modulus is not set");
+        m
+    }
+
+    pub fn val(&self) -> u64 {
+        self.val
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = *self;
+        let mut res = DynModInt::new(1, self.modulus);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// Multiplicative inverse by extended Euclid. `None` if `val` and `modulus` are not coprime.
+    pub fn inverse(&self) -> Option<Self> {
+        let (mut a, mut b) = (self.val as i128, self.modulus as i128);
+        let (mut x, mut y) = (1_i128, 0_i128);
+        while b != 0 {
+            let q = a / b;
+            a -= q * b;
+            x -= q * y;
+            std::mem::swap(&mut a, &mut b);
+            std::mem::swap(&mut x, &mut y);
+        }
+        if a != 1 {
+            return None;
+        }
+        let m = self.modulus as i128;
+        Some(DynModInt::new(((x % m + m) % m) as u64, self.modulus))
+    }
+
+    /// Multiplicative inverse, which also exists for composite `modulus` if `val` is coprime to it.
+    /// Panics otherwise.
+    pub fn inv(&self) -> Self {
+        match self.inverse() {
+            Some(x) => x,
+            None => panic!(
+                "DynModInt: {} is not invertible modulo {}",
+                self.val, self.modulus
+            ),
+        }
+    }
+
+    fn check_modulus(&self, other: &Self) {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "DynModInt: operands have different moduli"
+        );
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl From<u64> for DynModInt {
+    fn from(val: u64) -> Self {
+        DynModInt::new(val, DynModInt::global_modulus())
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl From<i64> for DynModInt {
+    fn from(val: i64) -> Self {
+        let m = DynModInt::global_modulus() as i128;
+        DynModInt::new(((val as i128 % m + m) % m) as u64, m as u64)
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::iter::Sum for DynModInt {
+    fn sum<I: Iterator<Item = Self>>(mut iter: I) -> Self {
+        match iter.next() {
+            Some(first) => iter.fold(first, |acc, x| acc + x),
+            None => DynModInt::from(0_u64),
+        }
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::iter::Product for DynModInt {
+    fn product<I: Iterator<Item = Self>>(mut iter: I) -> Self {
+        match iter.next() {
+            Some(first) => iter.fold(first, |acc, x| acc * x),
+            None => DynModInt::from(1_u64),
+        }
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::fmt::Display for DynModInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::ops::Neg for DynModInt {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        DynModInt::new(self.modulus - self.val, self.modulus)
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::ops::Add for DynModInt {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.check_modulus(&rhs);
+        let val = self.val + rhs.val;
+        DynModInt {
+            val: if val >= self.modulus {
+                val - self.modulus
+            } else {
+                val
+            },
+            modulus: self.modulus,
+        }
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::ops::Sub for DynModInt {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.check_modulus(&rhs);
+        self + (-rhs)
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::ops::Mul for DynModInt {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.check_modulus(&rhs);
+        let val = self.val as u128 * rhs.val as u128 % self.modulus as u128;
+        DynModInt {
+            val: val as u64,
+            modulus: self.modulus,
+        }
+    }
+}
+
+#[snippet("dyn_mod_int")]
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl std::ops::Div for DynModInt {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        self.check_modulus(&rhs);
+        self * rhs.inv()
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::ops::AddAssign for DynModInt {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::ops::SubAssign for DynModInt {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::ops::MulAssign for DynModInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[snippet("dyn_mod_int")]
+impl std::ops::DivAssign for DynModInt {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    type Mint = ModInt998244353;
+
+    #[test]
+    fn test_mod_int_identities() {
+        let mut rng = XorShift::new();
+        let mut next = || Mint::new(rng.next_u64());
+        for _ in 0..100 {
+            let (a, b, c) = (next(), next(), next());
+            assert_eq!(a + b, b + a);
+            assert_eq!(a * b, b * a);
+            assert_eq!((a + b) * c, a * c + b * c);
+            assert_eq!(a - b + b, a);
+            assert_eq!(a + (-a), Mint::new(0));
+            if b.val() != 0 {
+                assert_eq!(a / b * b, a);
+                assert_eq!(b * b.inv(), Mint::new(1));
+            }
+        }
+        let mut c = Mint::new(10);
+        c += Mint::new(5);
+        c -= Mint::new(20);
+        c *= Mint::new(3);
+        c /= Mint::new(3);
+        assert_eq!(c, Mint::from(-5_i64));
+    }
+
+    #[test]
+    fn test_mod_int_from_negative() {
+        assert_eq!(Mint::from(-1_i64).val(), 998_244_352);
+        assert_eq!(Mint::from(-998_244_353_i64).val(), 0);
+        assert_eq!(
+            ModInt1000000007::from(-1_000_000_008_i64).val(),
+            1_000_000_006
+        );
+        assert_eq!(Mint::from(998_244_354_u64).val(), 1);
+    }
+
+    #[test]
+    fn test_mod_int_pow() {
+        assert_eq!(Mint::new(0).pow(0).val(), 1);
+        assert_eq!(Mint::new(0).pow(5).val(), 0);
+        assert_eq!(Mint::new(3).pow(998_244_352).val(), 1);
+        assert_eq!(ModInt1000000007::new(2).pow(10).to_string(), "1024");
+    }
+
+    #[test]
+    fn test_mod_int_sum_product() {
+        let v = (1..=10).map(Mint::new).collect::<Vec<_>>();
+        assert_eq!(v.iter().cloned().sum::<Mint>().val(), 55);
+        assert_eq!(v.into_iter().product::<Mint>().val(), 3_628_800);
+    }
+
+    #[test]
+    #[should_panic(expected = "0 is not invertible")]
+    fn test_mod_int_divide_by_zero() {
+        let _ = Mint::new(1) / Mint::new(0);
+    }
+
+    #[test]
+    fn test_dyn_mod_int_arithmetic() {
+        let m = 13;
+        let (a, b) = (DynModInt::new(10, m), DynModInt::new(5, m));
+        assert_eq!(a + b, DynModInt::new(2, m));
+        assert_eq!(b - a, DynModInt::new(8, m));
+        assert_eq!(a * b, DynModInt::new(11, m));
+        assert_eq!(a / b, DynModInt::new(2, m));
+        assert_eq!(-DynModInt::new(0, m), DynModInt::new(0, m));
+        let mut c = a;
+        c += b;
+        c *= b;
+        assert_eq!(c, DynModInt::new(10, m));
+        assert_eq!(DynModInt::new(20, m).val(), 7);
+    }
+
+    #[test]
+    fn test_dyn_mod_int_pow() {
+        assert_eq!(DynModInt::new(3, 7).pow(6), DynModInt::new(1, 7));
+        assert_eq!(DynModInt::new(2, 1_000_000_007).pow(0).val(), 1);
+        let m = (1 << 61) - 1;
+        assert_eq!(DynModInt::new(m - 1, m).pow(2).val(), 1);
+    }
+
+    #[test]
+    fn test_dyn_mod_int_inverse() {
+        assert_eq!(DynModInt::new(2, 4).inverse(), None);
+        assert_eq!(DynModInt::new(3, 7).inverse(), Some(DynModInt::new(5, 7)));
+        assert_eq!(DynModInt::new(0, 1).inverse(), Some(DynModInt::new(0, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "different moduli")]
+    fn test_dyn_mod_int_different_moduli() {
+        let _ = DynModInt::new(1, 7) + DynModInt::new(1, 11);
+    }
+
+    #[test]
+    fn test_dyn_mod_int_global_modulus() {
+        let m = "1000000007".parse::<u64>().unwrap();
+        DynModInt::set_modulus(m);
+        let n = 20;
+        let mut fact = vec![DynModInt::from(1_u64); n + 1];
+        for i in 1..=n {
+            fact[i] = fact[i - 1] * DynModInt::from(i as u64);
+        }
+        let mut finv = vec![DynModInt::from(1_u64); n + 1];
+        finv[n] = fact[n].inv();
+        for i in (1..=n).rev() {
+            finv[i - 1] = finv[i] * DynModInt::from(i as u64);
+        }
+        let e = crate::math::enumerator::Enumerator::new(n, m as usize);
+        for i in 0..=n {
+            assert_eq!(fact[i].val() as usize, e.factorial(i));
+            assert_eq!(fact[i] * finv[i], DynModInt::from(1_u64));
+        }
+        let choose = fact[10] * finv[4] * finv[6];
+        assert_eq!(choose.val() as usize, e.choose(10, 4));
+        assert_eq!(DynModInt::from(-1_i64).val(), m - 1);
+        assert_eq!(
+            (1..=5_u64).map(DynModInt::from).sum::<DynModInt>().val(),
+            15
+        );
+        assert_eq!(
+            (1..=5_u64).map(DynModInt::from).product::<DynModInt>(),
+            fact[5]
+        );
+        assert_eq!(std::iter::empty().product::<DynModInt>().val(), 1);
+    }
+
+    #[test]
+    fn test_dyn_mod_int_large_modulus() {
+        let m = (1 << 62) - 57;
+        DynModInt::set_modulus(m);
+        let a = DynModInt::from(-2_i64);
+        assert_eq!((a * a).val(), 4);
+        assert_eq!((a + a).val(), m - 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "8 is not invertible modulo 12")]
+    fn test_dyn_mod_int_inv_not_coprime() {
+        DynModInt::set_modulus(12);
+        assert_eq!(DynModInt::from(5_u64).inv().val(), 5);
+        DynModInt::from(8_u64).inv();
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus is not set")]
+    fn test_dyn_mod_int_modulus_not_set() {
+        std::thread::spawn(|| DynModInt::from(1_u64))
+            .join()
+            .unwrap_or_else(|e| std::panic::resume_unwind(e));
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be in [1, 2^62]")]
+    fn test_dyn_mod_int_new_modulus_too_large() {
+        assert_eq!(DynModInt::new(5, 1 << 62).val(), 5);
+        DynModInt::new(0, (1 << 62) + 1);
+    }
+}
@@ -0,0 +1,304 @@
+use crate::math::mod_int::{ModInt1000000007, ModInt998244353};
+use cargo_snippet::snippet;
+
+#[snippet("ntt")]
+const NTT_MOD: u64 = 998_244_353;
+
+#[snippet("ntt")]
+const NTT_ROOT: u64 = 3;
+
+#[snippet("ntt")]
+fn ntt_pow(mut a: u64, mut e: u64) -> u64 {
+    let mut res = 1;
+    a %= NTT_MOD;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = res * a % NTT_MOD;
+        }
+        a = a * a % NTT_MOD;
+        e >>= 1;
+    }
+    res
+}
+
+#[snippet("ntt")]
+/// In-place number theoretic transform over `998244353`.
+/// `a.len()` must be a power of two.
+fn ntt(a: &mut [u64], invert: bool) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let mut w = ntt_pow(NTT_ROOT, (NTT_MOD - 1) / len as u64);
+        if invert {
+            w = ntt_pow(w, NTT_MOD - 2);
+        }
+        for i in (0..n).step_by(len) {
+            let mut wn = 1;
+            for k in 0..len / 2 {
+                let u = a[i + k];
+                let v = a[i + k + len / 2] * wn % NTT_MOD;
+                a[i + k] = (u + v) % NTT_MOD;
+                a[i + k + len / 2] = (u + NTT_MOD - v) % NTT_MOD;
+                wn = wn * w % NTT_MOD;
+            }
+        }
+        len <<= 1;
+    }
+    if invert {
+        let n_inv = ntt_pow(n as u64, NTT_MOD - 2);
+        for x in a.iter_mut() {
+            *x = *x * n_inv % NTT_MOD;
+        }
+    }
+}
+
+#[snippet("ntt")]
+/// Convolution of `a` and `b` modulo `998244353`
+/// with time-complexity `O((n + m) log (n + m))`.
+pub fn convolution(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let mut fa = a.iter().map(|&x| x % NTT_MOD).collect::<Vec<_>>();
+    let mut fb = b.iter().map(|&x| x % NTT_MOD).collect::<Vec<_>>();
+    fa.resize(n, 0);
+    fb.resize(n, 0);
+    ntt(&mut fa, false);
+    ntt(&mut fb, false);
+    for (x, y) in fa.iter_mut().zip(fb.iter()) {
+        *x = *x * y % NTT_MOD;
+    }
+    ntt(&mut fa, true);
+    fa.truncate(len);
+    fa
+}
+
+#[snippet("polynomial", include = "ntt")]
+/// Coefficient type of `Poly`, choosing how polynomials are multiplied.
+pub trait Convolution:
+    Copy + Default + std::ops::Add<Output = Self> + std::ops::Mul<Output = Self>
+{
+    /// Naive convolution with time-complexity `O(nm)`, overridden where a faster one exists.
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        if a.is_empty() || b.is_empty() {
+            return vec![];
+        }
+        let mut res = vec![Self::default(); a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                res[i + j] = res[i + j] + x * y;
+            }
+        }
+        res
+    }
+}
+
+#[snippet("polynomial", include = "ntt")]
+macro_rules! impl_naive_convolution {
+    ($($t:ty),*) => {
+        $(impl Convolution for $t {})*
+    };
+}
+
+#[snippet("polynomial", include = "ntt")]
+impl_naive_convolution!(i32, i64, i128, u32, u64, u128, usize, f64, ModInt1000000007);
+
+#[snippet("polynomial", include = "ntt")]
+#[snippet(include = "mod_int")]
+impl Convolution for ModInt998244353 {
+    /// Convolution by NTT with time-complexity `O((n + m) log (n + m))`.
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        let a = a.iter().map(|x| x.val()).collect::<Vec<_>>();
+        let b = b.iter().map(|x| x.val()).collect::<Vec<_>>();
+        convolution(&a, &b).into_iter().map(Self::new).collect()
+    }
+}
+
+#[snippet("polynomial", include = "ntt")]
+/// Polynomial whose `i`th coefficient is that of `x^i`.
+///
+/// Trailing zero (`T::default()`) coefficients are trimmed on construction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Poly<T> {
+    coeffs: Vec<T>,
+}
+
+#[snippet("polynomial", include = "ntt")]
+impl<T> Poly<T>
+where
+    T: Copy + Default + PartialEq + std::ops::Add<Output = T> + std::ops::Mul<Output = T>,
+{
+    pub fn new(coeffs: Vec<T>) -> Self {
+        let mut coeffs = coeffs;
+        while coeffs.last() == Some(&T::default()) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    /// Degree of the polynomial. Zero polynomial is regarded to have degree `0`.
+    pub fn degree(&self) -> usize {
+        self.coeffs.len().saturating_sub(1)
+    }
+
+    /// Evaluate at `x` by Horner's method.
+    pub fn eval(&self, x: T) -> T {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(T::default(), |acc, &c| acc * x + c)
+    }
+
+    /// Multiply by `T::convolve`, which is NTT for `ModInt998244353`
+    /// and naive with time-complexity `O(nm)` otherwise.
+    pub fn mul(&self, other: &Self) -> Self
+    where
+        T: Convolution,
+    {
+        Self::new(T::convolve(&self.coeffs, &other.coeffs))
+    }
+
+    pub fn derivative(&self) -> Self
+    where
+        T: From<u32>,
+    {
+        Self::new(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| T::from(i as u32) * c)
+                .collect(),
+        )
+    }
+
+    /// Antiderivative whose constant term is `const_term`. `T` is expected to be a field.
+    pub fn integral(&self, const_term: T) -> Self
+    where
+        T: From<u32> + std::ops::Div<Output = T>,
+    {
+        let mut coeffs = vec![const_term];
+        for (i, &c) in self.coeffs.iter().enumerate() {
+            coeffs.push(c / T::from(i as u32 + 1));
+        }
+        Self::new(coeffs)
+    }
+}
+
+#[snippet("polynomial", include = "ntt")]
+impl Poly<u64> {
+    /// Multiply by NTT regarding coefficients as residues modulo `998244353`.
+    pub fn mul_ntt(&self, other: &Self) -> Self {
+        Self::new(convolution(&self.coeffs, &other.coeffs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_trims_trailing_zeros() {
+        let p = Poly::new(vec![1, 2, 0, 0]);
+        assert_eq!(p.coeffs(), &[1, 2]);
+        assert_eq!(p.degree(), 1);
+        assert_eq!(Poly::<i64>::new(vec![0]).degree(), 0);
+    }
+
+    #[test]
+    fn test_mul() {
+        let p = Poly::new(vec![1, 1]);
+        assert_eq!(p.mul(&p), Poly::new(vec![1, 2, 1]));
+        assert_eq!(p.mul(&Poly::new(vec![])), Poly::new(vec![]));
+    }
+
+    #[test]
+    fn test_eval() {
+        let p = Poly::new(vec![1, 1, 1]);
+        assert_eq!(p.eval(2), 7);
+    }
+
+    #[test]
+    fn test_derivative() {
+        let p = Poly::new(vec![0_i64, 1, 0, 1]);
+        assert_eq!(p.derivative(), Poly::new(vec![1, 0, 3]));
+    }
+
+    #[test]
+    fn test_integral() {
+        let p = Poly::new(vec![1.0, 0.0, 3.0]);
+        assert_eq!(p.integral(2.0), Poly::new(vec![2.0, 1.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_derivative_and_integral_mod_int() {
+        type Mint = ModInt998244353;
+        let p = Poly::new(vec![Mint::new(5), Mint::new(3), Mint::new(7)]);
+        let dp = p.derivative();
+        assert_eq!(dp, Poly::new(vec![Mint::new(3), Mint::new(14)]));
+        assert_eq!(dp.integral(Mint::new(5)), p);
+        let q = Poly::new(vec![Mint::new(1), Mint::new(1)]);
+        let iq = q.integral(Mint::new(0));
+        assert_eq!(iq.coeffs[2] * Mint::new(2), Mint::new(1));
+        assert_eq!(iq.derivative(), q);
+    }
+
+    #[test]
+    fn test_mul_ntt_matches_naive() {
+        let a = (0..50).map(|i| i * i % 1000).collect::<Vec<_>>();
+        let b = (0..37).map(|i| (i + 7) * 31 % 1000).collect::<Vec<_>>();
+        let (pa, pb) = (Poly::new(a), Poly::new(b));
+        assert_eq!(pa.mul_ntt(&pb), pa.mul(&pb));
+    }
+
+    #[test]
+    fn test_mul_mod_int_uses_ntt() {
+        type Mint = ModInt998244353;
+        let a = (0..300).map(|i| Mint::new(i * i + 1)).collect::<Vec<_>>();
+        let b = (0..200)
+            .map(|i| Mint::from(-(i as i64)))
+            .collect::<Vec<_>>();
+        let naive = {
+            let mut res = vec![Mint::new(0); a.len() + b.len() - 1];
+            for (i, &x) in a.iter().enumerate() {
+                for (j, &y) in b.iter().enumerate() {
+                    res[i + j] += x * y;
+                }
+            }
+            res
+        };
+        let (pa, pb) = (Poly::new(a.clone()), Poly::new(b.clone()));
+        assert_eq!(pa.mul(&pb).coeffs(), &naive[..]);
+        let to_u64 = |v: &[Mint]| v.iter().map(|x| x.val()).collect::<Vec<_>>();
+        let expected = Poly::new(to_u64(&a)).mul_ntt(&Poly::new(to_u64(&b)));
+        assert_eq!(to_u64(pa.mul(&pb).coeffs()), expected.coeffs());
+    }
+
+    #[test]
+    fn test_convolution() {
+        assert_eq!(convolution(&[1, 1], &[1, 1]), vec![1, 2, 1]);
+        assert_eq!(
+            convolution(&[NTT_MOD - 1], &[2, 3]),
+            vec![NTT_MOD - 2, NTT_MOD - 3]
+        );
+        assert_eq!(convolution(&[], &[1]), vec![]);
+    }
+}
@@ -0,0 +1,105 @@
+use cargo_snippet::snippet;
+
+#[snippet("pow_monoid")]
+/// `base` raised to `exp` under associative `mul` with `identity`,
+/// by repeated squaring with `O(log exp)` multiplications.
+pub fn pow_monoid<T: Clone, F: Fn(&T, &T) -> T>(base: T, exp: u64, mul: F, identity: T) -> T {
+    let mut res = identity;
+    let mut base = base;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = mul(&res, &base);
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = mul(&base, &base);
+        }
+    }
+    res
+}
+
+#[snippet("pow_monoid")]
+/// `base` raised to `exp` under associative `mul` without identity.
+///
+/// Panics if `exp == 0`.
+pub fn pow_semigroup<T: Clone, F: Fn(&T, &T) -> T>(base: T, exp: u64, mul: F) -> T {
+    assert!(exp >= 1);
+    let mut res: Option<T> = None;
+    let mut base = base;
+    let mut exp = exp;
+    loop {
+        if exp & 1 == 1 {
+            res = Some(match res {
+                Some(r) => mul(&r, &base),
+                None => base.clone(),
+            });
+        }
+        exp >>= 1;
+        if exp == 0 {
+            break;
+        }
+        base = mul(&base, &base);
+    }
+    res.unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::ratio::Ratio;
+
+    type Matrix = [[u64; 2]; 2];
+
+    fn mat_mul(a: &Matrix, b: &Matrix) -> Matrix {
+        let mut c = [[0; 2]; 2];
+        for i in 0..2 {
+            for j in 0..2 {
+                for k in 0..2 {
+                    c[i][j] += a[i][k] * b[k][j];
+                }
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_pow_monoid_integer() {
+        assert_eq!(pow_monoid(2_u64, 10, |a, b| a * b, 1), 1024);
+        assert_eq!(pow_monoid(3_u64, 0, |a, b| a * b, 1), 1);
+        assert_eq!(pow_monoid(3_u64, 5, |a, b| a + b, 0), 15);
+    }
+
+    #[test]
+    fn test_pow_monoid_fibonacci_matrix() {
+        let fib = [[1, 1], [1, 0]];
+        let id = [[1, 0], [0, 1]];
+        assert_eq!(pow_monoid(fib, 10, mat_mul, id)[0][1], 55);
+        assert_eq!(
+            pow_monoid(fib, 90, mat_mul, id)[0][1],
+            2_880_067_194_370_816_120
+        );
+    }
+
+    #[test]
+    fn test_pow_monoid_ratio() {
+        let two = Ratio::from_integer(2);
+        let one = Ratio::from_integer(1);
+        assert_eq!(pow_monoid(two, 4, |a, b| *a * *b, one), Ratio::new(16, 1));
+    }
+
+    #[test]
+    fn test_pow_semigroup() {
+        assert_eq!(pow_semigroup(2_u64, 1, |a, b| a * b), 2);
+        assert_eq!(pow_semigroup(2_u64, 10, |a, b| a * b), 1024);
+        assert_eq!(pow_semigroup(1_u64, 1 << 40, |a, b| a * b), 1);
+        let s = pow_semigroup("ab".to_string(), 3, |a, b| format!("{}{}", a, b));
+        assert_eq!(s, "ababab");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pow_semigroup_panics_with_zero_exponent() {
+        pow_semigroup(2_u64, 0, |a, b| a * b);
+    }
+}
@@ -0,0 +1,82 @@
+use cargo_snippet::snippet;
+
+#[snippet("count_primes")]
+/// Count primes not greater than `n` by Lucy_Hedgehog's method
+/// with time-complexity `O(n^(3/4))`.
+///
+/// https://projecteuler.net/thread=10;page=5#111677
+pub fn count_primes(n: u64) -> u64 {
+    if n < 2 {
+        return 0;
+    }
+    let mut sq = (n as f64).sqrt() as u64;
+    while sq * sq > n {
+        sq -= 1;
+    }
+    while (sq + 1) * (sq + 1) <= n {
+        sq += 1;
+    }
+    let sq = sq as usize;
+
+    // `small[v]` = Number of survivors in [2, `v`].
+    // `large[i]` = Number of survivors in [2, `n / i`].
+    let mut small = (0..=sq as u64)
+        .map(|v| v.saturating_sub(1))
+        .collect::<Vec<_>>();
+    let mut large = std::iter::once(0)
+        .chain((1..=sq as u64).map(|i| n / i - 1))
+        .collect::<Vec<_>>();
+
+    for p in 2..=sq {
+        if small[p] == small[p - 1] {
+            continue;
+        }
+        let pc = small[p - 1];
+        let p2 = (p * p) as u64;
+        let end = sq.min((n / p2) as usize);
+        for i in 1..=end {
+            let d = i * p;
+            let s = if d <= sq {
+                large[d]
+            } else {
+                small[(n / d as u64) as usize]
+            };
+            large[i] -= s - pc;
+        }
+        for v in (p * p..=sq).rev() {
+            small[v] -= small[v / p] - pc;
+        }
+    }
+    large[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::eratosthenes::Eratosthenes;
+
+    #[test]
+    fn test_count_primes() {
+        assert_eq!(count_primes(0), 0);
+        assert_eq!(count_primes(1), 0);
+        assert_eq!(count_primes(2), 1);
+        assert_eq!(count_primes(10), 4);
+        assert_eq!(count_primes(100), 25);
+        assert_eq!(count_primes(1_000_000), 78498);
+        assert_eq!(count_primes(1_000_000_000), 50_847_534);
+    }
+
+    #[test]
+    fn test_count_primes_matches_sieve() {
+        let e = Eratosthenes::new(100_000);
+        let mut cnt = 0;
+        for n in 0..=100_000 {
+            if e.is_prime(n) {
+                cnt += 1;
+            }
+            if n % 997 == 0 || n < 1_000 {
+                assert_eq!(count_primes(n as u64), cnt);
+            }
+        }
+    }
+}
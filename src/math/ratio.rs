@@ -60,6 +60,56 @@ impl Ratio {
             denominator: self.numerator,
         }
     }
+
+    pub fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Clamp into [`lo`, `hi`]. Panics if `lo > hi`.
+    pub fn clamp(self, lo: Self, hi: Self) -> Self {
+        assert!(lo <= hi);
+        self.max(lo).min(hi)
+    }
+}
+#[snippet("ratio")]
+impl Default for Ratio {
+    fn default() -> Self {
+        Ratio::from_integer(0)
+    }
+}
+#[snippet("ratio")]
+impl std::str::FromStr for Ratio {
+    type Err = String;
+    /// Parse either `"num/den"` or a bare integer `"num"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut iter = s.trim().splitn(2, '/');
+        let parse = |t: &str| {
+            t.trim()
+                .parse::<i64>()
+                .map_err(|e| format!("Ratio: {}: {:?}", e, s))
+        };
+        let num = parse(iter.next().unwrap())?;
+        let den = match iter.next() {
+            Some(t) => parse(t)?,
+            None => 1,
+        };
+        if den == 0 {
+            return Err(format!("Ratio: divide by zero: {:?}", s));
+        }
+        Ok(Ratio::new(num, den))
+    }
 }
 #[snippet("ratio")]
 impl PartialOrd for Ratio {
@@ -221,4 +271,55 @@ mod tests {
         let c = Ratio::new(21, 10);
         assert_eq!(a / b, c);
     }
+
+    #[test]
+    fn test_ratio_min_max() {
+        let a = Ratio::new(3, 5);
+        let b = Ratio::new(2, 7);
+        assert_eq!(a.min(b), b);
+        assert_eq!(a.max(b), a);
+    }
+
+    #[test]
+    fn test_ratio_clamp() {
+        let lo = Ratio::new(1, 3);
+        let hi = Ratio::new(1, 2);
+        assert_eq!(Ratio::new(1, 4).clamp(lo, hi), lo);
+        assert_eq!(Ratio::new(2, 5).clamp(lo, hi), Ratio::new(2, 5));
+        assert_eq!(Ratio::new(3, 4).clamp(lo, hi), hi);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ratio_clamp_with_inverted_range() {
+        Ratio::new(1, 4).clamp(Ratio::new(1, 2), Ratio::new(1, 3));
+    }
+
+    #[test]
+    fn test_ratio_from_str() {
+        assert_eq!("3/5".parse::<Ratio>(), Ok(Ratio::new(3, 5)));
+        assert_eq!("6/10".parse::<Ratio>(), Ok(Ratio::new(3, 5)));
+        assert_eq!("7".parse::<Ratio>(), Ok(Ratio::from_integer(7)));
+        assert_eq!("-1/2".parse::<Ratio>(), Ok(Ratio::new(-1, 2)));
+        assert_eq!("1/-2".parse::<Ratio>(), Ok(Ratio::new(-1, 2)));
+        assert_eq!("+3/5".parse::<Ratio>(), Ok(Ratio::new(3, 5)));
+    }
+
+    #[test]
+    fn test_ratio_from_str_error() {
+        assert!("1/0".parse::<Ratio>().is_err());
+        assert!("abc".parse::<Ratio>().is_err());
+        assert!("1/2/3".parse::<Ratio>().is_err());
+        assert!("".parse::<Ratio>().is_err());
+        assert!("1/".parse::<Ratio>().is_err());
+    }
+
+    #[test]
+    fn test_ratio_default() {
+        assert_eq!(Ratio::default(), Ratio::from_integer(0));
+        let sum = [Ratio::new(1, 2), Ratio::new(1, 3), Ratio::new(1, 6)]
+            .iter()
+            .fold(Ratio::default(), |acc, &x| acc + x);
+        assert_eq!(sum, Ratio::from_integer(1));
+    }
 }
@@ -0,0 +1,120 @@
+use cargo_snippet::snippet;
+
+#[snippet("xor_basis")]
+/// Linear basis of `u64` values over GF(2).
+///
+/// * `basis`: `basis[b]` is the basis vector whose highest set bit is `b`, or `0` if absent.
+#[derive(Debug, Clone)]
+pub struct XorBasis {
+    basis: [u64; 64],
+    rank: usize,
+}
+
+#[snippet("xor_basis")]
+impl Default for XorBasis {
+    fn default() -> Self {
+        Self {
+            basis: [0; 64],
+            rank: 0,
+        }
+    }
+}
+
+#[snippet("xor_basis")]
+impl XorBasis {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reduce `x` by the basis from the highest bit.
+    fn reduce(&self, x: u64) -> u64 {
+        let mut x = x;
+        for b in (0..64).rev() {
+            if x >> b & 1 == 1 && self.basis[b] != 0 {
+                x ^= self.basis[b];
+            }
+        }
+        x
+    }
+
+    /// Insert `x` and returns whether the rank increased.
+    pub fn insert(&mut self, x: u64) -> bool {
+        let x = self.reduce(x);
+        if x == 0 {
+            return false;
+        }
+        self.basis[63 - x.leading_zeros() as usize] = x;
+        self.rank += 1;
+        true
+    }
+
+    /// Tests if `x` is representable as XOR of some inserted values.
+    pub fn contains(&self, x: u64) -> bool {
+        self.reduce(x) == 0
+    }
+
+    /// Largest XOR of some (possibly none) inserted values.
+    pub fn max_xor(&self) -> u64 {
+        self.basis.iter().rev().fold(0, |acc, &v| acc.max(acc ^ v))
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subset_xors(a: &[u64]) -> Vec<u64> {
+        (0..1 << a.len())
+            .map(|s: usize| {
+                (0..a.len())
+                    .filter(|&i| s >> i & 1 == 1)
+                    .fold(0, |acc, i| acc ^ a[i])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_and_rank() {
+        let mut b = XorBasis::new();
+        assert!(b.insert(0b101));
+        assert!(b.insert(0b011));
+        assert!(!b.insert(0b110));
+        assert!(!b.insert(0));
+        assert_eq!(b.rank(), 2);
+    }
+
+    #[test]
+    fn test_max_xor_matches_brute_force() {
+        let samples = [
+            vec![],
+            vec![0],
+            vec![3, 10, 5, 25, 2, 8],
+            vec![9, 8, 5],
+            vec![1 << 63, (1 << 63) | 1, 7, 12],
+        ];
+        for a in &samples {
+            let mut b = XorBasis::new();
+            for &x in a {
+                b.insert(x);
+            }
+            assert_eq!(b.max_xor(), *subset_xors(a).iter().max().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let a = [3, 10, 5, 25];
+        let mut b = XorBasis::new();
+        for &x in &a {
+            b.insert(x);
+        }
+        let xors = subset_xors(&a);
+        for x in 0..64 {
+            assert_eq!(b.contains(x), xors.contains(&x));
+        }
+    }
+}
@@ -1,5 +1,35 @@
 use cargo_snippet::snippet;
 
+#[snippet("adjacent_grids")]
+/// Offsets to 4 neighbors, where `!0` stands for `-1` by wrapping addition.
+pub const DIR4: [(usize, usize); 4] = [(0, 1), (1, 0), (0, !0), (!0, 0)];
+
+#[snippet("adjacent_grids")]
+/// Offsets to 8 neighbors, where `!0` stands for `-1` by wrapping addition.
+pub const DIR8: [(usize, usize); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, !0),
+    (0, !0),
+    (!0, !0),
+    (!0, 0),
+    (!0, 1),
+];
+
+#[snippet("adjacent_grids")]
+/// Offsets of knight moves, where `!0 - 1` stands for `-2` by wrapping addition.
+pub const DIR_KNIGHT: [(usize, usize); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, !0),
+    (1, !0 - 1),
+    (!0, !0 - 1),
+    (!0 - 1, !0),
+    (!0 - 1, 1),
+    (!0, 2),
+];
+
 #[snippet]
 pub fn adjacent_grids(
     i: usize,
@@ -27,7 +57,7 @@ pub fn adjacent_grids_4(
     height: usize,
     width: usize,
 ) -> impl Iterator<Item = (usize, usize)> {
-    adjacent_grids(i, j, height, width, &[(0, 1), (1, 0), (0, !0), (!0, 0)])
+    adjacent_grids(i, j, height, width, &DIR4)
 }
 
 #[snippet(include = "adjacent_grids")]
@@ -37,27 +67,268 @@ pub fn adjacent_grids_8(
     height: usize,
     width: usize,
 ) -> impl Iterator<Item = (usize, usize)> {
-    adjacent_grids(
-        i,
-        j,
-        height,
-        width,
-        &[
-            (0, 1),
-            (1, 1),
-            (1, 0),
-            (1, !0),
-            (0, !0),
-            (!0, !0),
-            (!0, 0),
-            (!0, 1),
-        ],
-    )
+    adjacent_grids(i, j, height, width, &DIR8)
+}
+
+#[snippet(include = "adjacent_grids")]
+pub fn adjacent_grids_knight(
+    i: usize,
+    j: usize,
+    height: usize,
+    width: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    adjacent_grids(i, j, height, width, &DIR_KNIGHT)
+}
+
+#[snippet]
+/// Same as `adjacent_grids` but with signed `offsets`.
+pub fn adjacent_grids_with(
+    i: usize,
+    j: usize,
+    height: usize,
+    width: usize,
+    offsets: &[(i64, i64)],
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    assert!(height < !0 && width < !0);
+    offsets.iter().filter_map(move |&(di, dj)| {
+        let ni = i.wrapping_add(di as usize);
+        let nj = j.wrapping_add(dj as usize);
+        if ni < height && nj < width {
+            Some((ni, nj))
+        } else {
+            None
+        }
+    })
+}
+
+#[snippet("flat_index")]
+/// Index of `(i, j)` in a grid of `width` stored in a flat vector in row-major order.
+pub fn to_flat(i: usize, j: usize, width: usize) -> usize {
+    debug_assert!(j < width);
+    i * width + j
+}
+
+#[snippet("flat_index")]
+/// Inverse of `to_flat`.
+pub fn from_flat(idx: usize, width: usize) -> (usize, usize) {
+    (idx / width, idx % width)
+}
+
+#[snippet("cells_within")]
+/// Cells within the grid at Manhattan distance at most `k` from `(i, j)` in row-major order.
+pub fn cells_within_manhattan(
+    i: usize,
+    j: usize,
+    k: usize,
+    height: usize,
+    width: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    let rows = i.saturating_sub(k)..i.saturating_add(k + 1).min(height);
+    rows.flat_map(move |ni| {
+        let rem = k - (ni.max(i) - ni.min(i));
+        (j.saturating_sub(rem)..j.saturating_add(rem + 1).min(width)).map(move |nj| (ni, nj))
+    })
+}
+
+#[snippet("cells_within")]
+/// Cells within the grid at Manhattan distance exactly `k` from `(i, j)` in row-major order.
+pub fn cells_within_manhattan_exactly(
+    i: usize,
+    j: usize,
+    k: usize,
+    height: usize,
+    width: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    assert!(height < !0 && width < !0);
+    let rows = i.saturating_sub(k)..i.saturating_add(k + 1).min(height);
+    rows.flat_map(move |ni| {
+        let rem = k - (ni.max(i) - ni.min(i));
+        (0..=2 * rem)
+            .step_by((2 * rem).max(1))
+            .map(move |d| j.wrapping_sub(rem).wrapping_add(d))
+            .filter(move |&nj| nj < width)
+            .map(move |nj| (ni, nj))
+    })
+}
+
+#[snippet("cells_within")]
+/// Cells within the grid at Chebyshev distance at most `k` from `(i, j)` in row-major order.
+pub fn cells_within_chebyshev(
+    i: usize,
+    j: usize,
+    k: usize,
+    height: usize,
+    width: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    let rows = i.saturating_sub(k)..i.saturating_add(k + 1).min(height);
+    rows.flat_map(move |ni| {
+        (j.saturating_sub(k)..j.saturating_add(k + 1).min(width)).map(move |nj| (ni, nj))
+    })
+}
+
+#[snippet("cells_within")]
+/// Cells within the grid at Chebyshev distance exactly `k` from `(i, j)` in row-major order.
+pub fn cells_within_chebyshev_exactly(
+    i: usize,
+    j: usize,
+    k: usize,
+    height: usize,
+    width: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    assert!(height < !0 && width < !0);
+    let rows = i.saturating_sub(k)..i.saturating_add(k + 1).min(height);
+    rows.flat_map(move |ni| {
+        // Only the top and bottom rows are filled, other rows have two ends.
+        let step = if ni + k == i || ni == i + k { 1 } else { 2 * k };
+        (0..=2 * k)
+            .step_by(step)
+            .map(move |d| j.wrapping_sub(k).wrapping_add(d))
+            .filter(move |&nj| nj < width)
+            .map(move |nj| (ni, nj))
+    })
+}
+
+#[snippet("bfs_grid", include = "adjacent_grids")]
+/// Distances from the nearest of `sources` moving in 4 directions through cells satisfying `passable`,
+/// with time-complexity `O(HW)`. Unreachable cells are `!0`.
+pub fn bfs_grid<F: Fn(usize, usize) -> bool>(
+    height: usize,
+    width: usize,
+    sources: &[(usize, usize)],
+    passable: F,
+) -> Vec<Vec<u32>> {
+    bfs_grid_with(height, width, sources, passable, &DIR4)
+}
+
+#[snippet("bfs_grid", include = "adjacent_grids")]
+/// Same as `bfs_grid` but moving by `directions` such as `DIR8` or `DIR_KNIGHT`.
+pub fn bfs_grid_with<F: Fn(usize, usize) -> bool>(
+    height: usize,
+    width: usize,
+    sources: &[(usize, usize)],
+    passable: F,
+    directions: &[(usize, usize)],
+) -> Vec<Vec<u32>> {
+    let mut dist = vec![vec![!0; width]; height];
+    let mut queue = std::collections::VecDeque::new();
+    for &(i, j) in sources {
+        if dist[i][j] != 0 {
+            dist[i][j] = 0;
+            queue.push_back((i, j));
+        }
+    }
+    while let Some((i, j)) = queue.pop_front() {
+        for (ni, nj) in adjacent_grids(i, j, height, width, directions) {
+            if dist[ni][nj] == !0 && passable(ni, nj) {
+                dist[ni][nj] = dist[i][j] + 1;
+                queue.push_back((ni, nj));
+            }
+        }
+    }
+    dist
+}
+
+#[snippet]
+/// Windows of length `size` starting at every `step` elements.
+/// Trailing elements not filling a whole window are dropped like `slice::windows`.
+pub fn windows_step<T>(s: &[T], size: usize, step: usize) -> impl Iterator<Item = &[T]> {
+    assert!(size > 0 && step > 0);
+    s.windows(size).step_by(step)
+}
+
+#[snippet]
+/// All submasks of `mask` in decreasing order, from `mask` itself down to `0`.
+pub fn subsets(mask: u32) -> impl Iterator<Item = u32> {
+    std::iter::successors(Some(mask), move |&sub| {
+        if sub == 0 {
+            None
+        } else {
+            Some((sub - 1) & mask)
+        }
+    })
+}
+
+#[snippet]
+/// All `n`-bit masks with exactly `k` bits set in increasing order by Gosper's hack.
+pub fn combinations_of_bits(n: u32, k: u32) -> impl Iterator<Item = u32> {
+    assert!(n <= 32);
+    let first = if k <= n { Some((1_u64 << k) - 1) } else { None };
+    std::iter::successors(first, move |&x| {
+        if x == 0 {
+            return None;
+        }
+        let c = x & x.wrapping_neg();
+        let r = x + c;
+        let y = (((r ^ x) >> 2) / c) | r;
+        if y < 1 << n {
+            Some(y)
+        } else {
+            None
+        }
+    })
+    .map(|x| x as u32)
+}
+
+#[snippet]
+/// Indices of set bits of `mask` in increasing order.
+pub fn bit_positions(mask: u32) -> impl Iterator<Item = u32> {
+    std::iter::successors(Some(mask), |&m| Some(m & m.wrapping_sub(1)))
+        .take_while(|&m| m != 0)
+        .map(|m| m.trailing_zeros())
+}
+
+#[snippet]
+/// `n`-th Gray code, which differs from the previous one in exactly one bit.
+pub fn gray_code(n: u32) -> u32 {
+    n ^ (n >> 1)
+}
+
+#[snippet("run_length")]
+/// Iterator over maximal runs of equal elements `(&element, length)` of a slice.
+pub struct Runs<'a, T> {
+    s: &'a [T],
+}
+
+#[snippet("run_length")]
+impl<'a, T: PartialEq> Iterator for Runs<'a, T> {
+    type Item = (&'a T, usize);
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.s.first()?;
+        let len = self.s.iter().take_while(|&x| x == first).count();
+        self.s = &self.s[len..];
+        Some((first, len))
+    }
+}
+
+#[snippet("run_length")]
+/// `s.runs()` iterates over maximal runs of equal elements lazily.
+pub trait RunsExt<T> {
+    fn runs(&self) -> Runs<'_, T>;
+}
+
+#[snippet("run_length")]
+impl<T: PartialEq> RunsExt<T> for [T] {
+    fn runs(&self) -> Runs<'_, T> {
+        Runs { s: self }
+    }
+}
+
+#[snippet("run_length")]
+pub fn run_length_encode<T: PartialEq + Clone>(s: &[T]) -> Vec<(T, usize)> {
+    s.runs().map(|(x, len)| (x.clone(), len)).collect()
+}
+
+#[snippet("run_length")]
+pub fn run_length_decode<T: Clone>(runs: &[(T, usize)]) -> Vec<T> {
+    runs.iter()
+        .flat_map(|(x, len)| (0..*len).map(move |_| x.clone()))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{random_array, XorShift};
 
     #[test]
     fn test_adjacent_grids_out_of_bounds() {
@@ -123,4 +394,272 @@ mod tests {
             assert_eq!(result, expected);
         }
     }
+
+    #[test]
+    fn test_adjacent_grids_knight() {
+        let mut corner = adjacent_grids_knight(0, 0, 8, 8).collect::<Vec<_>>();
+        corner.sort_unstable();
+        assert_eq!(corner, vec![(1, 2), (2, 1)]);
+
+        let mut edge = adjacent_grids_knight(1, 7, 8, 8).collect::<Vec<_>>();
+        edge.sort_unstable();
+        assert_eq!(edge, vec![(0, 5), (2, 5), (3, 6)]);
+
+        assert_eq!(adjacent_grids_knight(4, 4, 8, 8).count(), 8);
+        assert_eq!(adjacent_grids_knight(0, 0, 2, 2).next(), None);
+    }
+
+    #[test]
+    fn test_adjacent_grids_with_signed_offsets() {
+        let knight = [
+            (1, 2),
+            (2, 1),
+            (2, -1),
+            (1, -2),
+            (-1, -2),
+            (-2, -1),
+            (-2, 1),
+            (-1, 2),
+        ];
+        for i in 0..5 {
+            for j in 0..6 {
+                let signed = adjacent_grids_with(i, j, 5, 6, &knight).collect::<Vec<_>>();
+                let wrapped = adjacent_grids(i, j, 5, 6, &DIR_KNIGHT).collect::<Vec<_>>();
+                assert_eq!(signed, wrapped);
+            }
+        }
+        let dirs = DIR4
+            .iter()
+            .map(|&(di, dj)| (di as i64, dj as i64))
+            .collect::<Vec<_>>();
+        assert_eq!(dirs, vec![(0, 1), (1, 0), (0, -1), (-1, 0)]);
+    }
+
+    #[test]
+    fn test_flat_index_round_trip() {
+        let (h, w) = (4, 7);
+        let mut expected = 0;
+        for i in 0..h {
+            for j in 0..w {
+                assert_eq!(to_flat(i, j, w), expected);
+                assert_eq!(from_flat(to_flat(i, j, w), w), (i, j));
+                expected += 1;
+            }
+        }
+        assert!((0..h * w).all(|k| {
+            let (i, j) = from_flat(k, w);
+            to_flat(i, j, w) == k
+        }));
+    }
+
+    fn naive_cells<F: Fn(usize, usize) -> bool>(h: usize, w: usize, f: F) -> Vec<(usize, usize)> {
+        (0..h)
+            .flat_map(|i| (0..w).map(move |j| (i, j)))
+            .filter(|&(i, j)| f(i, j))
+            .collect()
+    }
+
+    #[test]
+    fn test_cells_within_counts_away_from_borders() {
+        for k in 0..5 {
+            let (c, size) = (10, 21);
+            assert_eq!(
+                cells_within_manhattan(c, c, k, size, size).count(),
+                2 * k * k + 2 * k + 1
+            );
+            assert_eq!(
+                cells_within_manhattan_exactly(c, c, k, size, size).count(),
+                if k == 0 { 1 } else { 4 * k }
+            );
+            assert_eq!(
+                cells_within_chebyshev(c, c, k, size, size).count(),
+                (2 * k + 1) * (2 * k + 1)
+            );
+            assert_eq!(
+                cells_within_chebyshev_exactly(c, c, k, size, size).count(),
+                if k == 0 { 1 } else { 8 * k }
+            );
+        }
+    }
+
+    #[test]
+    fn test_cells_within_clipped_matches_naive() {
+        let (h, w) = (4, 6);
+        let dist = |a: usize, b: usize| a.max(b) - a.min(b);
+        for &(i, j) in [(0, 0), (3, 5), (0, 4), (2, 1)].iter() {
+            for k in 0..9 {
+                let m = |ni, nj| dist(i, ni) + dist(j, nj);
+                let c = |ni, nj| dist(i, ni).max(dist(j, nj));
+                assert_eq!(
+                    cells_within_manhattan(i, j, k, h, w).collect::<Vec<_>>(),
+                    naive_cells(h, w, |ni, nj| m(ni, nj) <= k)
+                );
+                assert_eq!(
+                    cells_within_manhattan_exactly(i, j, k, h, w).collect::<Vec<_>>(),
+                    naive_cells(h, w, |ni, nj| m(ni, nj) == k)
+                );
+                assert_eq!(
+                    cells_within_chebyshev(i, j, k, h, w).collect::<Vec<_>>(),
+                    naive_cells(h, w, |ni, nj| c(ni, nj) <= k)
+                );
+                assert_eq!(
+                    cells_within_chebyshev_exactly(i, j, k, h, w).collect::<Vec<_>>(),
+                    naive_cells(h, w, |ni, nj| c(ni, nj) == k)
+                );
+            }
+        }
+        assert_eq!(
+            cells_within_manhattan(0, 0, 1, 2, 2).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_bfs_grid_wall_ring() {
+        let maze = ["S....", ".###.", ".#.#.", ".###.", "....."];
+        let dist = bfs_grid(5, 5, &[(0, 0)], |i, j| maze[i].as_bytes()[j] != b'#');
+        assert_eq!(dist[0][4], 4);
+        assert_eq!(dist[4][4], 8);
+        assert_eq!(dist[1][1], !0);
+        assert_eq!(dist[2][2], !0);
+        let dist8 = bfs_grid_with(5, 5, &[(0, 0)], |i, j| maze[i].as_bytes()[j] != b'#', &DIR8);
+        assert_eq!(dist8[4][4], 7);
+        assert_eq!(dist8[2][2], !0);
+    }
+
+    #[test]
+    fn test_bfs_grid_multi_source_is_min_of_single_sources() {
+        let (h, w) = (6, 7);
+        let passable = |i: usize, j: usize| (i * 7 + j * 3) % 5 != 0 || i == j;
+        let sources = [(0, 0), (5, 6), (2, 4)];
+        let multi = bfs_grid(h, w, &sources, passable);
+        let singles = sources
+            .iter()
+            .map(|&s| bfs_grid(h, w, &[s], passable))
+            .collect::<Vec<_>>();
+        for i in 0..h {
+            for j in 0..w {
+                assert_eq!(multi[i][j], singles.iter().map(|d| d[i][j]).min().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_bfs_grid_single_cell() {
+        assert_eq!(bfs_grid(1, 1, &[(0, 0)], |_, _| true), vec![vec![0]]);
+        assert_eq!(bfs_grid(1, 1, &[], |_, _| true), vec![vec![!0]]);
+    }
+
+    #[test]
+    fn test_windows_step() {
+        let s = [1, 2, 3, 4, 5];
+        let w = windows_step(&s, 2, 2).collect::<Vec<_>>();
+        assert_eq!(w, vec![&[1, 2], &[3, 4]]);
+        let w = windows_step(&s, 2, 3).collect::<Vec<_>>();
+        assert_eq!(w, vec![&[1, 2], &[4, 5]]);
+        let w = windows_step(&s, 1, 4).collect::<Vec<_>>();
+        assert_eq!(w, vec![&[1], &[5]]);
+        assert_eq!(windows_step(&s, 6, 1).next(), None);
+    }
+
+    #[test]
+    fn test_windows_step_one_matches_windows() {
+        let s = [1, 2, 3, 4, 5];
+        for size in 1..=5 {
+            assert!(windows_step(&s, size, 1).eq(s.windows(size)));
+        }
+    }
+
+    #[test]
+    fn test_subsets() {
+        assert_eq!(
+            subsets(0b101).collect::<Vec<_>>(),
+            vec![0b101, 0b100, 0b001, 0]
+        );
+        assert_eq!(subsets(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(subsets(0b1101_0110).count(), 1 << 5);
+        assert!(subsets(0b1101_0110).all(|sub| sub & !0b1101_0110 == 0));
+        assert_eq!(
+            subsets(!0).take(3).collect::<Vec<_>>(),
+            vec![!0, !0 - 1, !0 - 2]
+        );
+    }
+
+    #[test]
+    fn test_combinations_of_bits() {
+        assert_eq!(
+            combinations_of_bits(4, 2).collect::<Vec<_>>(),
+            vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]
+        );
+        let binom = |n: usize, k: usize| (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1));
+        for n in 0..=12 {
+            for k in 0..=n {
+                let masks = combinations_of_bits(n, k).collect::<Vec<_>>();
+                assert_eq!(masks.len(), binom(n as usize, k as usize));
+                assert!(masks.windows(2).all(|w| w[0] < w[1]));
+                assert!(masks.iter().all(|&m| m.count_ones() == k && m >> n == 0));
+            }
+        }
+        assert_eq!(combinations_of_bits(5, 0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(combinations_of_bits(3, 4).count(), 0);
+        assert_eq!(combinations_of_bits(32, 32).collect::<Vec<_>>(), vec![!0]);
+        assert_eq!(combinations_of_bits(32, 1).last(), Some(1 << 31));
+    }
+
+    #[test]
+    fn test_bit_positions() {
+        assert_eq!(
+            bit_positions(0b1010_0101).collect::<Vec<_>>(),
+            vec![0, 2, 5, 7]
+        );
+        assert_eq!(bit_positions(0).count(), 0);
+        assert_eq!(bit_positions(!0).count(), 32);
+        assert_eq!(bit_positions(1 << 31).collect::<Vec<_>>(), vec![31]);
+    }
+
+    #[test]
+    fn test_gray_code() {
+        assert_eq!((0..4).map(gray_code).collect::<Vec<_>>(), vec![0, 1, 3, 2]);
+        for n in 1..1024 {
+            assert_eq!((gray_code(n) ^ gray_code(n - 1)).count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn test_run_length_encode() {
+        assert_eq!(run_length_encode::<u8>(&[]), vec![]);
+        assert_eq!(run_length_encode(b"aaaa"), vec![(b'a', 4)]);
+        assert_eq!(
+            run_length_encode(b"abab"),
+            vec![(b'a', 1), (b'b', 1), (b'a', 1), (b'b', 1)]
+        );
+        assert_eq!(
+            run_length_encode(&[1, 1, 2, 3, 3, 3]),
+            vec![(1, 2), (2, 1), (3, 3)]
+        );
+        assert_eq!(
+            run_length_decode(&[('x', 2), ('y', 0), ('z', 1)]),
+            vec!['x', 'x', 'z']
+        );
+    }
+
+    #[test]
+    fn test_runs_is_lazy_and_borrowing() {
+        let s = [String::from("a"), String::from("a"), String::from("b")];
+        let mut runs = s.runs();
+        assert_eq!(runs.next(), Some((&s[0], 2)));
+        assert_eq!(runs.next(), Some((&s[2], 1)));
+        assert_eq!(runs.next(), None);
+    }
+
+    #[test]
+    fn test_run_length_round_trip() {
+        let mut rng = XorShift::new();
+        for len in 0..50 {
+            let s = random_array(&mut rng, len, 4);
+            let encoded = run_length_encode(&s);
+            assert!(encoded.windows(2).all(|w| w[0].0 != w[1].0));
+            assert_eq!(run_length_decode(&encoded), s);
+        }
+    }
 }
@@ -0,0 +1,139 @@
+use cargo_snippet::snippet;
+
+#[snippet("eertree")]
+#[derive(Debug, Clone)]
+struct EertreeNode {
+    len: isize,
+    link: usize,
+    children: Vec<(u8, usize)>,
+}
+
+#[snippet("eertree")]
+/// Palindromic tree (eertree) of a byte string.
+///
+/// Every node except the two roots is a distinct palindromic substring.
+/// `nodes[0]` is the imaginary root of length `-1` and `nodes[1]` is the empty palindrome,
+/// whose suffix links point to `nodes[0]`.
+#[derive(Debug, Clone)]
+pub struct Eertree {
+    nodes: Vec<EertreeNode>,
+    suffix: Vec<usize>,
+}
+
+#[snippet("eertree")]
+impl Eertree {
+    /// Constructs a new tree of `s` with time-complexity `O(n)` up to the alphabet size.
+    pub fn new(s: &[u8]) -> Self {
+        let root = |len| EertreeNode {
+            len,
+            link: 0,
+            children: vec![],
+        };
+        let mut nodes = vec![root(-1), root(0)];
+        let mut suffix = Vec::with_capacity(s.len());
+        let mut last = 1;
+        for (i, &c) in s.iter().enumerate() {
+            // Longest palindromic suffix `v` of `&s[..i]` which extends to `c v c`.
+            let extends = |v: usize, nodes: &[EertreeNode]| {
+                let j = i as isize - nodes[v].len - 1;
+                j >= 0 && s[j as usize] == c
+            };
+            let mut v = last;
+            while !extends(v, &nodes) {
+                v = nodes[v].link;
+            }
+            let child = nodes[v].children.iter().find(|&&(k, _)| k == c);
+            if let Some(&(_, u)) = child {
+                last = u;
+            } else {
+                let len = nodes[v].len + 2;
+                let link = if len == 1 {
+                    1
+                } else {
+                    let mut w = nodes[v].link;
+                    while !extends(w, &nodes) {
+                        w = nodes[w].link;
+                    }
+                    nodes[w].children.iter().find(|&&(k, _)| k == c).unwrap().1
+                };
+                last = nodes.len();
+                nodes.push(EertreeNode {
+                    len,
+                    link,
+                    children: vec![],
+                });
+                nodes[v].children.push((c, last));
+            }
+            suffix.push(last);
+        }
+        Self { nodes, suffix }
+    }
+
+    pub fn count_distinct_palindromes(&self) -> usize {
+        self.nodes.len() - 2
+    }
+
+    /// Length of the longest palindromic suffix of `&s[..=i]`.
+    pub fn longest_palindrome_suffix_at(&self, i: usize) -> usize {
+        self.nodes[self.suffix[i]].len as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+    use std::collections::BTreeSet;
+
+    fn brute_force(s: &[u8]) -> (usize, Vec<usize>) {
+        let is_palindrome = |t: &[u8]| t.iter().eq(t.iter().rev());
+        let mut set = BTreeSet::new();
+        let mut longest = vec![0; s.len()];
+        for r in 1..=s.len() {
+            for l in (0..r).rev() {
+                if is_palindrome(&s[l..r]) {
+                    set.insert(&s[l..r]);
+                    longest[r - 1] = r - l;
+                }
+            }
+        }
+        (set.len(), longest)
+    }
+
+    #[test]
+    fn test_count_distinct_palindromes() {
+        // "a", "b", "aa", "aba" and "aabaa"
+        assert_eq!(Eertree::new(b"aabaa").count_distinct_palindromes(), 5);
+        // "a", "b", "c", "bcb" and "abcba"
+        assert_eq!(Eertree::new(b"abcba").count_distinct_palindromes(), 5);
+        assert_eq!(Eertree::new(b"").count_distinct_palindromes(), 0);
+        assert_eq!(Eertree::new(b"aaaa").count_distinct_palindromes(), 4);
+    }
+
+    #[test]
+    fn test_longest_palindrome_suffix_at() {
+        let t = Eertree::new(b"aabaa");
+        let res = (0..5)
+            .map(|i| t.longest_palindrome_suffix_at(i))
+            .collect::<Vec<_>>();
+        assert_eq!(res, vec![1, 2, 1, 3, 5]);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = XorShift::new();
+        for n in 0..=20 {
+            for _ in 0..20 {
+                let s = (0..n)
+                    .map(|_| b'a' + rng.below(3) as u8)
+                    .collect::<Vec<_>>();
+                let t = Eertree::new(&s);
+                let (count, longest) = brute_force(&s);
+                assert_eq!(t.count_distinct_palindromes(), count);
+                for (i, &len) in longest.iter().enumerate() {
+                    assert_eq!(t.longest_palindrome_suffix_at(i), len);
+                }
+            }
+        }
+    }
+}
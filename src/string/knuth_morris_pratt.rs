@@ -6,11 +6,89 @@ pub struct KnuthMorrisPratt<'a, T: PartialEq> {
     target: &'a [T],
 }
 
+#[snippet("knuth_morris_pratt")]
+/// Iterator over start indices where a pattern occurs in a target.
+/// Empty pattern occurs at every index in `0..=n`.
+///
+/// If `overlap` is `false`, search resumes from the end of the previous match.
+pub struct Matches<'b, T: PartialEq> {
+    target: &'b [T],
+    pattern: &'b [T],
+    fail: std::borrow::Cow<'b, [usize]>,
+    overlap: bool,
+    i: usize,
+    j: usize,
+    done: bool,
+}
+
+#[snippet("knuth_morris_pratt")]
+impl<'b, T: PartialEq> Matches<'b, T> {
+    fn new(target: &'b [T], pattern: &'b [T], overlap: bool) -> Self {
+        let fail = KnuthMorrisPratt::failure_function(pattern);
+        Self::with_fail(target, pattern, std::borrow::Cow::Owned(fail), overlap)
+    }
+
+    fn with_fail(
+        target: &'b [T],
+        pattern: &'b [T],
+        fail: std::borrow::Cow<'b, [usize]>,
+        overlap: bool,
+    ) -> Self {
+        Self {
+            target,
+            pattern,
+            fail,
+            overlap,
+            i: 0,
+            j: 0,
+            done: false,
+        }
+    }
+}
+
+#[snippet("knuth_morris_pratt")]
+impl<'b, T: PartialEq> Iterator for Matches<'b, T> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (n, m) = (self.target.len(), self.pattern.len());
+        if m == 0 {
+            self.i += 1;
+            self.done = self.i > n;
+            return Some(self.i - 1);
+        }
+        while self.i < n {
+            if self.target[self.i] == self.pattern[self.j] {
+                self.i += 1;
+                self.j += 1;
+                // Matched
+                if self.j == m {
+                    self.j = if self.overlap { self.fail[self.j] } else { 0 };
+                    return Some(self.i - m);
+                }
+            } else if self.j > 0 {
+                self.j = self.fail[self.j];
+            } else {
+                self.i += 1;
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+#[snippet("knuth_morris_pratt")]
+impl<'b, T: PartialEq> std::iter::FusedIterator for Matches<'b, T> {}
+
 #[snippet("knuth_morris_pratt")]
 impl<'a, T: PartialEq> KnuthMorrisPratt<'a, T> {
-    fn get_failure_function(pattern: &[T]) -> Vec<usize> {
-        // `fail[j]` = Length of the longest proper prefix of `&pattern[0..j]`
-        // which is also a suffix of the slice.
+    /// Failure function of `pattern`.
+    ///
+    /// `fail[j]` = Length of the longest proper prefix of `&pattern[0..j]`
+    /// which is also a suffix of the slice, i.e. the longest border of it.
+    pub fn failure_function(pattern: &[T]) -> Vec<usize> {
         let m = pattern.len();
         let mut fail = vec![0; m + 1];
 
@@ -35,30 +113,278 @@ impl<'a, T: PartialEq> KnuthMorrisPratt<'a, T> {
         Self { target }
     }
 
+    /// Iterate over all start indices where `pattern` occur lazily
+    pub fn find_iter<'b>(&'b self, pattern: &'b [T]) -> Matches<'b, T> {
+        Matches::new(self.target, pattern, true)
+    }
+
     /// Find all start indices where `pattern` occur
     pub fn find_all(&self, pattern: &[T]) -> Vec<usize> {
+        self.find_iter(pattern).collect()
+    }
+
+    /// Find all start indices where `pattern` occur, comparing elements by `key`
+    pub fn find_all_by_key<K: PartialEq>(
+        &self,
+        pattern: &[T],
+        key: impl Fn(&T) -> K,
+    ) -> Vec<usize> {
+        let target = self.target.iter().map(&key).collect::<Vec<_>>();
+        let pattern = pattern.iter().map(&key).collect::<Vec<_>>();
+        Matches::new(&target, &pattern, true).collect()
+    }
+
+    /// Find the first start index where `pattern` occur
+    pub fn find_first(&self, pattern: &[T]) -> Option<usize> {
+        self.find_iter(pattern).next()
+    }
+
+    /// Find the last start index where `pattern` occur
+    pub fn find_last(&self, pattern: &[T]) -> Option<usize> {
         let (n, m) = (self.target.len(), pattern.len());
-        let mut indices = vec![];
-        let fail = Self::get_failure_function(pattern);
-        let (mut i, mut j) = (0, 0);
-        while i < n {
-            if self.target[i] == pattern[j] {
-                i += 1;
-                j += 1;
-                // Matched
-                if j == m {
-                    indices.push(i - m);
-                    j = fail[j];
-                }
-            } else if j > 0 {
-                j = fail[j];
-            } else {
-                i += 1;
+        let target = self.target.iter().rev().collect::<Vec<_>>();
+        let pattern = pattern.iter().rev().collect::<Vec<_>>();
+        Matches::new(&target, &pattern, true)
+            .next()
+            .map(|i| n - m - i)
+    }
+
+    /// Count occurrences of `pattern` allowing overlaps
+    pub fn count(&self, pattern: &[T]) -> usize {
+        self.find_iter(pattern).count()
+    }
+
+    /// Count occurrences of `pattern` which do not overlap each other,
+    /// greedily taken from the left
+    pub fn count_nonoverlapping(&self, pattern: &[T]) -> usize {
+        Matches::new(self.target, pattern, false).count()
+    }
+
+    /// Tests if `pattern` occurs in the target
+    pub fn contains(&self, pattern: &[T]) -> bool {
+        self.find_first(pattern).is_some()
+    }
+
+    /// All lengths of proper non-empty borders of the target in descending order
+    pub fn borders(&self) -> Vec<usize> {
+        let fail = Self::failure_function(self.target);
+        let mut res = vec![];
+        let mut j = fail[self.target.len()];
+        while j > 0 {
+            res.push(j);
+            j = fail[j];
+        }
+        res
+    }
+
+    /// Length of the longest proper border of the target
+    pub fn longest_border(&self) -> usize {
+        Self::failure_function(self.target)[self.target.len()]
+    }
+}
+
+#[snippet("kmp_automaton", include = "knuth_morris_pratt")]
+/// KMP automaton with explicit transition table over alphabet `alphabet`.
+///
+/// State `s` is the length of the longest prefix of the pattern which is
+/// a suffix of the input so far, and state `m` (= pattern length) is accepting.
+/// Once reaching state `m`, the automaton built by `new` continues
+/// via the failure link, while that built by `new_absorbing` stays at `m`.
+pub struct KmpAutomaton {
+    alphabet: std::ops::Range<u8>,
+    table: Vec<Vec<usize>>,
+}
+
+#[snippet("kmp_automaton", include = "knuth_morris_pratt")]
+impl KmpAutomaton {
+    fn build(pattern: &[u8], alphabet: std::ops::Range<u8>, absorbing: bool) -> Self {
+        assert!(pattern.iter().all(|c| alphabet.contains(c)));
+        let m = pattern.len();
+        let k = alphabet.len();
+        let fail = KnuthMorrisPratt::failure_function(pattern);
+        let mut table = vec![vec![0; k]; m + 1];
+        for s in 0..=m {
+            for (i, c) in alphabet.clone().enumerate() {
+                table[s][i] = if s < m && pattern[s] == c {
+                    s + 1
+                } else if s == m && absorbing {
+                    m
+                } else if s == 0 {
+                    0
+                } else {
+                    table[fail[s]][i]
+                };
             }
         }
-        indices
+        Self { alphabet, table }
+    }
+
+    /// Constructs a new automaton which continues matching after acceptance,
+    /// with time-complexity `O(m |alphabet|)`.
+    pub fn new(pattern: &[u8], alphabet: std::ops::Range<u8>) -> Self {
+        Self::build(pattern, alphabet, false)
     }
+
+    /// Constructs a new automaton which stays at the accepting state once reached,
+    /// with time-complexity `O(m |alphabet|)`.
+    pub fn new_absorbing(pattern: &[u8], alphabet: std::ops::Range<u8>) -> Self {
+        Self::build(pattern, alphabet, true)
+    }
+
+    /// Next state from `state` with input `c`.
+    pub fn next(&self, state: usize, c: u8) -> usize {
+        assert!(self.alphabet.contains(&c));
+        self.table[state][(c - self.alphabet.start) as usize]
+    }
+
+    pub fn is_accepting(&self, state: usize) -> bool {
+        state == self.table.len() - 1
+    }
+
+    /// Transition table. `table[s][i]` is the next state from `s`
+    /// with input `alphabet.start + i`.
+    pub fn table(&self) -> &[Vec<usize>] {
+        &self.table
+    }
+}
+
+#[snippet("kmp_replace", include = "knuth_morris_pratt")]
+/// Replace non-overlapping occurrences of `pattern` in `target` from the left with `replacement`.
+pub fn replace_all<T: PartialEq + Clone>(target: &[T], pattern: &[T], replacement: &[T]) -> Vec<T> {
+    let mut res = vec![];
+    let mut last = 0;
+    for i in Matches::new(target, pattern, false) {
+        res.extend_from_slice(&target[last..i]);
+        res.extend_from_slice(replacement);
+        last = i + pattern.len();
+    }
+    res.extend_from_slice(&target[last..]);
+    res
+}
+
+#[snippet("kmp_replace", include = "knuth_morris_pratt")]
+/// Pieces of `target` separated by non-overlapping occurrences of `pattern` from the left.
+pub fn split_by_pattern<'a, T: PartialEq + Clone>(target: &'a [T], pattern: &[T]) -> Vec<&'a [T]> {
+    let mut res = vec![];
+    let mut last = 0;
+    for i in Matches::new(target, pattern, false) {
+        res.push(&target[last..i]);
+        last = i + pattern.len();
+    }
+    res.push(&target[last..]);
+    res
 }
+
+#[snippet("period", include = "knuth_morris_pratt")]
+/// Smallest `p` such that `s[i] == s[i + p]` for every valid `i`.
+/// It divides `s.len()` only if `s` is a repetition of `&s[..p]`.
+pub fn smallest_period<T: PartialEq>(s: &[T]) -> usize {
+    s.len() - KnuthMorrisPratt::failure_function(s)[s.len()]
+}
+
+#[snippet("period", include = "knuth_morris_pratt")]
+/// Smallest period `p` if `s` is a repetition of `&s[..p]` at least twice.
+pub fn is_periodic<T: PartialEq>(s: &[T]) -> Option<usize> {
+    let p = smallest_period(s);
+    if p < s.len() && s.len() % p == 0 {
+        Some(p)
+    } else {
+        None
+    }
+}
+
+#[snippet("period", include = "knuth_morris_pratt")]
+/// Maximum `k` such that `s` is a repetition of some slice `k` times.
+/// `1` if `s` is aperiodic (including empty `s`).
+pub fn repetition_count<T: PartialEq>(s: &[T]) -> usize {
+    is_periodic(s).map_or(1, |p| s.len() / p)
+}
+
+#[snippet("kmp_pattern", include = "knuth_morris_pratt")]
+/// Pattern with its failure function precomputed, to be searched in many targets.
+pub struct KmpPattern<T: PartialEq> {
+    pattern: Vec<T>,
+    fail: Vec<usize>,
+}
+
+#[snippet("kmp_pattern", include = "knuth_morris_pratt")]
+impl<T: PartialEq> KmpPattern<T> {
+    pub fn new(pattern: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            pattern: pattern.to_vec(),
+            fail: KnuthMorrisPratt::failure_function(pattern),
+        }
+    }
+
+    fn find_iter_in<'b>(&'b self, target: &'b [T]) -> Matches<'b, T> {
+        let fail = std::borrow::Cow::Borrowed(&self.fail[..]);
+        Matches::with_fail(target, &self.pattern, fail, true)
+    }
+
+    /// Find all start indices where the pattern occur in `target`
+    pub fn find_all_in(&self, target: &[T]) -> Vec<usize> {
+        self.find_iter_in(target).collect()
+    }
+
+    /// Find the first start index where the pattern occur in `target`
+    pub fn find_first_in(&self, target: &[T]) -> Option<usize> {
+        self.find_iter_in(target).next()
+    }
+
+    /// Count occurrences of the pattern in `target` allowing overlaps
+    pub fn count_in(&self, target: &[T]) -> usize {
+        self.find_iter_in(target).count()
+    }
+}
+
+#[snippet("kmp_matcher", include = "knuth_morris_pratt")]
+/// Online KMP matcher consuming target elements one at a time.
+pub struct KmpMatcher<T: PartialEq> {
+    pattern: Vec<T>,
+    fail: Vec<usize>,
+    matched: usize,
+}
+
+#[snippet("kmp_matcher", include = "knuth_morris_pratt")]
+impl<T: PartialEq> KmpMatcher<T> {
+    pub fn new(pattern: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            pattern: pattern.to_vec(),
+            fail: KnuthMorrisPratt::failure_function(pattern),
+            matched: 0,
+        }
+    }
+
+    /// Feed the next element and returns whether a match ends with it.
+    pub fn push(&mut self, x: T) -> bool {
+        let m = self.pattern.len();
+        if self.matched == m {
+            self.matched = self.fail[m];
+        }
+        if m == 0 {
+            return true;
+        }
+        while self.matched > 0 && self.pattern[self.matched] != x {
+            self.matched = self.fail[self.matched];
+        }
+        if self.pattern[self.matched] == x {
+            self.matched += 1;
+        }
+        self.matched == m
+    }
+
+    /// Length of the longest prefix of the pattern which is a suffix of the elements fed so far.
+    pub fn matched_len(&self) -> usize {
+        self.matched
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,21 +392,21 @@ mod tests {
     #[test]
     fn test_failure_function() {
         let text = "ABCABDA";
-        let fail = KnuthMorrisPratt::get_failure_function(text.as_bytes());
+        let fail = KnuthMorrisPratt::failure_function(text.as_bytes());
         assert_eq!(fail, vec![0, 0, 0, 0, 1, 2, 0, 1]);
     }
 
     #[test]
     fn test_failure_function_with_single_element() {
         let text = "A";
-        let fail = KnuthMorrisPratt::get_failure_function(text.as_bytes());
+        let fail = KnuthMorrisPratt::failure_function(text.as_bytes());
         assert_eq!(fail, vec![0, 0]);
     }
 
     #[test]
     fn test_failure_function_with_no_element() {
         let text = "";
-        let fail = KnuthMorrisPratt::get_failure_function(text.as_bytes());
+        let fail = KnuthMorrisPratt::failure_function(text.as_bytes());
         assert_eq!(fail, vec![0]);
     }
 
@@ -104,4 +430,371 @@ mod tests {
         let matched = text.find_all("AAA".as_bytes());
         assert_eq!(matched, vec![]);
     }
+
+    #[test]
+    fn test_find_all_empty_pattern() {
+        let text = KnuthMorrisPratt::new("ABC".as_bytes());
+        assert_eq!(text.find_all(&[]), vec![0, 1, 2, 3]);
+        let text = KnuthMorrisPratt::new("".as_bytes());
+        assert_eq!(text.find_all(&[]), vec![0]);
+    }
+
+    #[test]
+    fn test_find_first_and_last() {
+        let text = KnuthMorrisPratt::new("AABAACAADAABAABA".as_bytes());
+        assert_eq!(text.find_first("AABA".as_bytes()), Some(0));
+        assert_eq!(text.find_last("AABA".as_bytes()), Some(12));
+        assert_eq!(text.find_first("AAD".as_bytes()), Some(6));
+        assert_eq!(text.find_last("AAD".as_bytes()), Some(6));
+        assert!(text.contains("CAA".as_bytes()));
+    }
+
+    #[test]
+    fn test_find_first_and_last_empty_pattern() {
+        let text = KnuthMorrisPratt::new("ABC".as_bytes());
+        assert_eq!(text.find_first(&[]), Some(0));
+        assert_eq!(text.find_last(&[]), Some(3));
+        assert!(text.contains(&[]));
+    }
+
+    #[test]
+    fn test_find_first_and_last_no_match() {
+        let text = KnuthMorrisPratt::new("AAAA".as_bytes());
+        assert_eq!(text.find_first("ZZ".as_bytes()), None);
+        assert_eq!(text.find_last("ZZ".as_bytes()), None);
+        assert!(!text.contains("AAAAA".as_bytes()));
+    }
+
+    #[test]
+    fn test_count() {
+        let text = KnuthMorrisPratt::new("AAAA".as_bytes());
+        assert_eq!(text.count("AA".as_bytes()), 3);
+        assert_eq!(text.count_nonoverlapping("AA".as_bytes()), 2);
+        assert_eq!(text.count("B".as_bytes()), 0);
+        assert_eq!(text.count_nonoverlapping("B".as_bytes()), 0);
+    }
+
+    #[test]
+    fn test_count_agrees_with_find_all() {
+        let text = KnuthMorrisPratt::new("AABAACAADAABAABAABA".as_bytes());
+        for pattern in &["AABA", "A", "ABAAB", "AA", ""] {
+            let pattern = pattern.as_bytes();
+            assert_eq!(text.count(pattern), text.find_all(pattern).len());
+        }
+        assert_eq!(text.count_nonoverlapping("AABA".as_bytes()), 3);
+    }
+
+    #[test]
+    fn test_matcher_agrees_with_find_all() {
+        let target = "AABAACAADAABAABAABA".as_bytes();
+        let text = KnuthMorrisPratt::new(target);
+        for pattern in &["AABA", "A", "ABAAB", "AA", "Z"] {
+            let pattern = pattern.as_bytes();
+            let mut matcher = KmpMatcher::new(pattern);
+            let ends = target
+                .iter()
+                .enumerate()
+                .filter(|&(_, &c)| matcher.push(c))
+                .map(|(i, _)| i + 1 - pattern.len())
+                .collect::<Vec<_>>();
+            assert_eq!(ends, text.find_all(pattern));
+        }
+    }
+
+    #[test]
+    fn test_matcher_state() {
+        let mut matcher = KmpMatcher::new("ABAB".as_bytes());
+        let states = "ABABABX"
+            .bytes()
+            .map(|c| (matcher.push(c), matcher.matched_len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            states,
+            vec![
+                (false, 1),
+                (false, 2),
+                (false, 3),
+                (true, 4),
+                (false, 3),
+                (true, 4),
+                (false, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matcher_empty_pattern() {
+        let mut matcher = KmpMatcher::new(&[]);
+        assert!(matcher.push(1));
+        assert_eq!(matcher.matched_len(), 0);
+    }
+
+    #[test]
+    fn test_borders() {
+        let text = KnuthMorrisPratt::new("abacaba".as_bytes());
+        assert_eq!(text.borders(), vec![3, 1]);
+        assert_eq!(text.longest_border(), 3);
+
+        let text = KnuthMorrisPratt::new("aaaa".as_bytes());
+        assert_eq!(text.borders(), vec![3, 2, 1]);
+
+        let text = KnuthMorrisPratt::new("abcabdab".as_bytes());
+        assert_eq!(text.borders(), vec![2]);
+
+        let text = KnuthMorrisPratt::new("abc".as_bytes());
+        assert_eq!(text.borders(), vec![]);
+        assert_eq!(text.longest_border(), 0);
+
+        let text = KnuthMorrisPratt::new("".as_bytes());
+        assert_eq!(text.longest_border(), 0);
+    }
+
+    #[test]
+    fn test_borders_match_brute_force() {
+        let target = "abaababaabaababaababa".as_bytes();
+        let n = target.len();
+        let expected = (1..n)
+            .rev()
+            .filter(|&k| target[..k] == target[n - k..])
+            .collect::<Vec<_>>();
+        assert_eq!(KnuthMorrisPratt::new(target).borders(), expected);
+    }
+
+    #[test]
+    fn test_period() {
+        let s = "abcabcabc".as_bytes();
+        assert_eq!(smallest_period(s), 3);
+        assert_eq!(is_periodic(s), Some(3));
+        assert_eq!(repetition_count(s), 3);
+
+        let s = "aabaab".as_bytes();
+        assert_eq!(smallest_period(s), 3);
+        assert_eq!(is_periodic(s), Some(3));
+        assert_eq!(repetition_count(s), 2);
+
+        let s = "abcab".as_bytes();
+        assert_eq!(smallest_period(s), 3);
+        assert_eq!(is_periodic(s), None);
+        assert_eq!(repetition_count(s), 1);
+    }
+
+    #[test]
+    fn test_period_trivial() {
+        let s = "aaaa".as_bytes();
+        assert_eq!(is_periodic(s), Some(1));
+        assert_eq!(repetition_count(s), 4);
+
+        let s = "a".as_bytes();
+        assert_eq!(smallest_period(s), 1);
+        assert_eq!(is_periodic(s), None);
+
+        let s = "".as_bytes();
+        assert_eq!(smallest_period(s), 0);
+        assert_eq!(is_periodic(s), None);
+        assert_eq!(repetition_count(s), 1);
+    }
+
+    #[test]
+    fn test_automaton_agrees_with_find_all() {
+        let target = "AABAACAADAABAABAABA".as_bytes();
+        let text = KnuthMorrisPratt::new(target);
+        for pattern in &["AABA", "A", "ABAAB", "AA", ""] {
+            let pattern = pattern.as_bytes();
+            let automaton = KmpAutomaton::new(pattern, b'A'..b'E');
+            let mut state = 0;
+            let mut found = vec![];
+            if automaton.is_accepting(state) {
+                found.push(0);
+            }
+            for (i, &c) in target.iter().enumerate() {
+                state = automaton.next(state, c);
+                if automaton.is_accepting(state) {
+                    found.push(i + 1 - pattern.len());
+                }
+            }
+            assert_eq!(found, text.find_all(pattern));
+        }
+    }
+
+    #[test]
+    fn test_automaton_forbidden_substring_dp() {
+        // Count strings over {a, b, c} of length `len` which do not contain "aba".
+        let pattern = "aba".as_bytes();
+        let automaton = KmpAutomaton::new_absorbing(pattern, b'a'..b'd');
+        assert_eq!(automaton.table().len(), 4);
+        for len in 0..7 {
+            let mut dp = vec![0_u64; pattern.len() + 1];
+            dp[0] = 1;
+            for _ in 0..len {
+                let mut next = vec![0; pattern.len() + 1];
+                for (s, &cnt) in dp.iter().enumerate() {
+                    for c in b'a'..b'd' {
+                        next[automaton.next(s, c)] += cnt;
+                    }
+                }
+                dp = next;
+            }
+            let count = dp[..pattern.len()].iter().sum::<u64>();
+
+            let brute_force = (0..3_usize.pow(len))
+                .filter(|&x| {
+                    let s = (0..len)
+                        .map(|i| b'a' + (x / 3_usize.pow(i) % 3) as u8)
+                        .collect::<Vec<_>>();
+                    !KnuthMorrisPratt::new(&s).contains(pattern)
+                })
+                .count() as u64;
+            assert_eq!(count, brute_force);
+        }
+    }
+
+    #[test]
+    fn test_automaton_absorbing() {
+        let automaton = KmpAutomaton::new_absorbing("ab".as_bytes(), b'a'..b'c');
+        assert_eq!(automaton.next(2, b'a'), 2);
+        assert_eq!(automaton.next(2, b'b'), 2);
+        let automaton = KmpAutomaton::new("ab".as_bytes(), b'a'..b'c');
+        assert_eq!(automaton.next(2, b'a'), 1);
+        assert_eq!(automaton.next(2, b'b'), 0);
+    }
+
+    #[derive(Debug, Clone)]
+    struct Counted<'c>(u8, &'c std::cell::Cell<usize>);
+
+    impl<'c> PartialEq for Counted<'c> {
+        fn eq(&self, other: &Self) -> bool {
+            self.1.set(self.1.get() + 1);
+            self.0 == other.0
+        }
+    }
+
+    #[test]
+    fn test_find_iter_is_lazy() {
+        let cnt = std::cell::Cell::new(0);
+        let wrap = |s: &str| s.bytes().map(|c| Counted(c, &cnt)).collect::<Vec<_>>();
+        let target = wrap("ABABABABABABABABABABABAB");
+        let pattern = wrap("ABA");
+        let text = KnuthMorrisPratt::new(&target);
+
+        cnt.set(0);
+        let all = text.find_all(&pattern);
+        let full_cost = cnt.get();
+
+        cnt.set(0);
+        let first_two = text.find_iter(&pattern).take(2).collect::<Vec<_>>();
+        assert_eq!(first_two, all[..2].to_vec());
+        assert!(cnt.get() < full_cost / 2);
+    }
+
+    #[test]
+    fn test_find_iter_is_fused() {
+        let text = KnuthMorrisPratt::new("AABAACAADAABAABA".as_bytes());
+        let mut iter = text.find_iter("AABA".as_bytes());
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![0, 9, 12]);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = text.find_iter(&[]);
+        assert_eq!(iter.by_ref().count(), 17);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_find_all_by_key_case_insensitive() {
+        let text = KnuthMorrisPratt::new("abAB aBab ABAb".as_bytes());
+        let matched = text.find_all_by_key("ab".as_bytes(), |c| c.to_ascii_lowercase());
+        assert_eq!(matched, vec![0, 2, 5, 7, 10, 12]);
+        assert_eq!(text.find_all("ab".as_bytes()), vec![0, 7]);
+    }
+
+    #[test]
+    fn test_find_all_by_key_character_class() {
+        let text = KnuthMorrisPratt::new("a12b3c456".as_bytes());
+        let matched = text.find_all_by_key("00".as_bytes(), |c| c.is_ascii_digit());
+        assert_eq!(matched, vec![1, 6, 7]);
+    }
+
+    #[test]
+    fn test_replace_all_matches_str_replace() {
+        let cases = [
+            ("aaaa", "aa", "b"),
+            ("abcabcab", "abc", "X"),
+            ("abcab", "ab", ""),
+            ("xyz", "q", "QQ"),
+            ("aaa", "a", "aa"),
+            ("ab", "", "-"),
+            ("", "a", "b"),
+        ];
+        for &(target, pattern, replacement) in &cases {
+            let res = replace_all(
+                target.as_bytes(),
+                pattern.as_bytes(),
+                replacement.as_bytes(),
+            );
+            assert_eq!(res, target.replace(pattern, replacement).into_bytes());
+        }
+    }
+
+    #[test]
+    fn test_split_by_pattern_matches_str_split() {
+        let cases = [
+            ("aaaa", "aa"),
+            ("abcabcab", "abc"),
+            ("abcab", "ab"),
+            ("xyz", "q"),
+            ("ab", ""),
+            ("", "a"),
+        ];
+        for &(target, pattern) in &cases {
+            let res = split_by_pattern(target.as_bytes(), pattern.as_bytes());
+            let expected = target
+                .split(pattern)
+                .map(|s| s.as_bytes())
+                .collect::<Vec<_>>();
+            assert_eq!(res, expected);
+        }
+    }
+
+    #[test]
+    fn test_replace_and_split_non_u8() {
+        let target = [1, 2, 3, 1, 2, 1, 2, 3];
+        assert_eq!(replace_all(&target, &[1, 2, 3], &[0]), vec![0, 1, 2, 0]);
+        let pieces: Vec<&[i32]> = vec![&[], &[1, 2], &[]];
+        assert_eq!(split_by_pattern(&target, &[1, 2, 3]), pieces);
+    }
+
+    #[test]
+    fn test_pattern_agrees_with_target_search() {
+        let pattern = KmpPattern::new("ABA".as_bytes());
+        for target in &["ABABABA", "", "AB", "ABA", "XABAX", "AABAACAADAABAABA"] {
+            let target = target.as_bytes();
+            let text = KnuthMorrisPratt::new(target);
+            let pattern_bytes = "ABA".as_bytes();
+            assert_eq!(pattern.find_all_in(target), text.find_all(pattern_bytes));
+            assert_eq!(
+                pattern.find_first_in(target),
+                text.find_first(pattern_bytes)
+            );
+            assert_eq!(pattern.count_in(target), text.count(pattern_bytes));
+        }
+    }
+
+    #[test]
+    fn test_pattern_failure_function_is_precomputed() {
+        let cnt = std::cell::Cell::new(0);
+        let wrap = |s: &str| s.bytes().map(|c| Counted(c, &cnt)).collect::<Vec<_>>();
+        let pattern = KmpPattern::new(&wrap("AABAAB"));
+        assert!(cnt.get() > 0);
+
+        // Searching in an empty target requires no comparison at all.
+        cnt.set(0);
+        assert_eq!(pattern.find_all_in(&[]), vec![]);
+        assert_eq!(cnt.get(), 0);
+
+        // Otherwise, comparisons are bounded by `2n`, excluding the failure function.
+        let target = wrap("AABAABAAB");
+        cnt.set(0);
+        assert_eq!(pattern.find_all_in(&target), vec![0, 3]);
+        assert!(cnt.get() <= 2 * target.len());
+    }
 }
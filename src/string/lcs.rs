@@ -0,0 +1,134 @@
+use cargo_snippet::snippet;
+
+#[snippet("lcs")]
+/// Length of a longest common subsequence of `a` and `b`
+/// with time-complexity `O(nm)` and space-complexity `O(min(n, m))`.
+pub fn lcs_length<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut dp = vec![0; b.len() + 1];
+    for x in a {
+        // `diag` keeps `dp[j]` of the previous row.
+        let mut diag = 0;
+        for (j, y) in b.iter().enumerate() {
+            let up = dp[j + 1];
+            dp[j + 1] = if x == y { diag + 1 } else { up.max(dp[j]) };
+            diag = up;
+        }
+    }
+    dp[b.len()]
+}
+
+#[snippet("lcs")]
+/// One of longest common subsequences of `a` and `b`
+/// with time-complexity `O(nm)` and space-complexity `O(nm)`.
+pub fn lcs<T: PartialEq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    let (n, m) = (a.len(), b.len());
+    let mut dp = vec![vec![0; m + 1]; n + 1];
+    for i in 0..n {
+        for j in 0..m {
+            dp[i + 1][j + 1] = if a[i] == b[j] {
+                dp[i][j] + 1
+            } else {
+                dp[i][j + 1].max(dp[i + 1][j])
+            };
+        }
+    }
+    let mut res = Vec::with_capacity(dp[n][m]);
+    let (mut i, mut j) = (n, m);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            res.push(a[i - 1].clone());
+            i -= 1;
+            j -= 1;
+        } else if dp[i - 1][j] >= dp[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    res.reverse();
+    res
+}
+
+#[snippet("edit_distance")]
+/// Levenshtein distance between `a` and `b`, i.e. the minimum number of
+/// insertions, deletions and substitutions to turn `a` into `b`,
+/// with time-complexity `O(nm)` and space-complexity `O(min(n, m))`.
+pub fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut dp = (0..=b.len()).collect::<Vec<_>>();
+    for (i, x) in a.iter().enumerate() {
+        let mut diag = dp[0];
+        dp[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let up = dp[j + 1];
+            dp[j + 1] = (diag + (x != y) as usize).min(up + 1).min(dp[j] + 1);
+            diag = up;
+        }
+    }
+    dp[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    fn is_subsequence(s: &[u8], t: &[u8]) -> bool {
+        let mut it = t.iter();
+        s.iter().all(|c| it.any(|d| d == c))
+    }
+
+    #[test]
+    fn test_lcs_length() {
+        assert_eq!(lcs_length(b"ABCBDAB", b"BDCAB"), 4);
+        assert_eq!(lcs_length(b"BDCAB", b"ABCBDAB"), 4);
+        assert_eq!(lcs_length(b"", b"ABC"), 0);
+        assert_eq!(lcs_length(b"ABC", b""), 0);
+        assert_eq!(lcs_length(b"ABC", b"DEF"), 0);
+        assert_eq!(lcs_length(&[1, 2, 3], &[1, 2, 3]), 3);
+    }
+
+    #[test]
+    fn test_lcs_reconstruction() {
+        let (a, b) = (b"ABCBDAB", b"BDCAB");
+        let s = lcs(a, b);
+        assert_eq!(s.len(), 4);
+        assert!(is_subsequence(&s, a) && is_subsequence(&s, b));
+        assert!(lcs(b"", b"ABC").is_empty());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance(b"kitten", b"sitting"), 3);
+        assert_eq!(edit_distance(b"sitting", b"kitten"), 3);
+        assert_eq!(edit_distance(b"", b""), 0);
+        assert_eq!(edit_distance(b"", b"abc"), 3);
+        assert_eq!(edit_distance(b"abc", b""), 3);
+        assert_eq!(edit_distance(b"flaw", b"lawn"), 2);
+        assert_eq!(edit_distance(b"intention", b"execution"), 5);
+    }
+
+    #[test]
+    fn test_matches_full_table() {
+        let mut rng = XorShift::new();
+        for _ in 0..50 {
+            let a = (0..rng.below(12)).map(|_| rng.below(3)).collect::<Vec<_>>();
+            let b = (0..rng.below(12)).map(|_| rng.below(3)).collect::<Vec<_>>();
+            assert_eq!(lcs(&a, &b).len(), lcs_length(&a, &b));
+            let (n, m) = (a.len(), b.len());
+            let mut dp = vec![vec![0; m + 1]; n + 1];
+            for i in 0..=n {
+                for j in 0..=m {
+                    dp[i][j] = if i == 0 || j == 0 {
+                        i + j
+                    } else {
+                        let sub = dp[i - 1][j - 1] + (a[i - 1] != b[j - 1]) as usize;
+                        sub.min(dp[i - 1][j] + 1).min(dp[i][j - 1] + 1)
+                    };
+                }
+            }
+            assert_eq!(edit_distance(&a, &b), dp[n][m]);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+pub mod eertree;
 pub mod knuth_morris_pratt;
+pub mod lcs;
 pub mod rolling_hash;
+pub mod trie;
 pub mod z_algorithm;
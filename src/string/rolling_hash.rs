@@ -29,20 +29,45 @@ impl RollingHash {
         res
     }
 
-    pub fn new(target: &[u8], base: u64) -> Self {
-        let n = target.len();
-
-        let mut hash_acc = vec![0; n + 1];
-        let mut base_pow = vec![0; n + 1];
-        base_pow[0] = 1;
+    fn from_values(target: impl Iterator<Item = u64>, base: u64) -> Self {
+        let mut hash_acc = vec![0];
+        let mut base_pow = vec![1];
 
-        for i in 0..n {
-            hash_acc[i + 1] = Self::modulo(Self::mul(hash_acc[i], base) + target[i] as u64);
-            base_pow[i + 1] = Self::modulo(Self::mul(base_pow[i], base));
+        for (i, x) in target.enumerate() {
+            hash_acc.push(Self::modulo(Self::mul(hash_acc[i], base) + x));
+            base_pow.push(Self::modulo(Self::mul(base_pow[i], base)));
         }
         Self { hash_acc, base_pow }
     }
 
+    pub fn new(target: &[u8], base: u64) -> Self {
+        Self::from_values(target.iter().map(|&x| x as u64), base)
+    }
+
+    /// Hash bytes of `s`, same as `new(s.as_bytes(), base)`.
+    pub fn from_str(s: &str, base: u64) -> Self {
+        Self::new(s.as_bytes(), base)
+    }
+
+    /// Hash `chars` by their code points.
+    pub fn from_chars(chars: &[char], base: u64) -> Self {
+        Self::from_values(chars.iter().map(|&c| c as u64 % Self::MOD), base)
+    }
+
+    /// Hash `char` sequence of `s` so that indices of `query` and `find_all`
+    /// are character indices rather than byte offsets.
+    pub fn from_str_chars(s: &str, base: u64) -> Self {
+        Self::from_values(s.chars().map(|c| c as u64 % Self::MOD), base)
+    }
+
+    /// Map character indices of `s` (e.g. result of `find_all` on a hash
+    /// built by `from_str_chars`) to byte offsets of `s`.
+    pub fn char_to_byte_offsets(s: &str, char_indices: &[usize]) -> Vec<usize> {
+        let mut offsets = s.char_indices().map(|(b, _)| b).collect::<Vec<_>>();
+        offsets.push(s.len());
+        char_indices.iter().map(|&i| offsets[i]).collect()
+    }
+
     /// Get hash within range [`left`, `right`)
     pub fn query(&self, left: Option<usize>, right: Option<usize>) -> Option<u64> {
         let n = self.hash_acc.len() - 1;
@@ -70,6 +95,70 @@ impl RollingHash {
                 .collect::<Vec<_>>(),
         )
     }
+
+    /// Find all start indices of every pattern in `patterns` at once.
+    ///
+    /// Patterns are grouped by length so that the target is scanned
+    /// only once per distinct pattern length.
+    pub fn find_any(&self, patterns: &[Self]) -> Vec<Vec<usize>> {
+        let n = self.hash_acc.len() - 1;
+        let mut groups = std::collections::BTreeMap::new();
+        for (k, pattern) in patterns.iter().enumerate() {
+            let m = pattern.hash_acc.len() - 1;
+            if m > n {
+                continue;
+            }
+            groups
+                .entry(m)
+                .or_insert_with(std::collections::HashMap::new)
+                .entry(pattern.query(None, None).unwrap())
+                .or_insert_with(Vec::new)
+                .push(k);
+        }
+        let mut res = vec![vec![]; patterns.len()];
+        for (m, group) in groups {
+            for i in 0..=n - m {
+                if let Some(ks) = group.get(&self.query(Some(i), Some(i + m)).unwrap()) {
+                    for &k in ks {
+                        res[k].push(i);
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// Tests whether each pattern in `patterns` occurs at least once.
+    pub fn contains_which(&self, patterns: &[Self]) -> Vec<bool> {
+        self.find_any(patterns)
+            .iter()
+            .map(|indices| !indices.is_empty())
+            .collect()
+    }
+
+    /// Count distinct substrings of length `len` by their hashes.
+    pub fn count_distinct_substrings(&self, len: usize) -> usize {
+        let n = self.hash_acc.len() - 1;
+        if len > n {
+            return 0;
+        }
+        (0..=n - len)
+            .map(|i| self.query(Some(i), Some(i + len)).unwrap())
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    }
+
+    /// Count distinct non-empty substrings of all lengths
+    /// with time-complexity `O(n^2)`.
+    ///
+    /// Collisions become likely as `O(n^2)` hashes are compared,
+    /// so double hashing (e.g. with two different bases) is recommended.
+    pub fn count_all_distinct_substrings(&self) -> u64 {
+        let n = self.hash_acc.len() - 1;
+        (1..=n)
+            .map(|len| self.count_distinct_substrings(len) as u64)
+            .sum()
+    }
 }
 
 #[cfg(test)]
@@ -118,4 +207,86 @@ mod tests {
 
         assert_eq!(txt_hash.find_all(&ptn_hash), None);
     }
+
+    #[test]
+    fn test_count_distinct_substrings() {
+        let hash = RollingHash::new("abab".as_bytes(), 3);
+        assert_eq!(hash.count_distinct_substrings(1), 2);
+        assert_eq!(hash.count_distinct_substrings(2), 2);
+        assert_eq!(hash.count_distinct_substrings(3), 2);
+        assert_eq!(hash.count_distinct_substrings(4), 1);
+        assert_eq!(hash.count_distinct_substrings(5), 0);
+        assert_eq!(hash.count_all_distinct_substrings(), 7);
+    }
+
+    #[test]
+    fn test_from_str_chars_uses_char_indices() {
+        let base = 1_000_003;
+
+        let txt = "あいうあいabあい";
+        let ptn = "あい";
+        let by_bytes = RollingHash::new(txt.as_bytes(), base)
+            .find_all(&RollingHash::new(ptn.as_bytes(), base))
+            .unwrap();
+        let by_chars = RollingHash::from_str_chars(txt, base)
+            .find_all(&RollingHash::from_str_chars(ptn, base))
+            .unwrap();
+        assert_eq!(by_bytes, vec![0, 9, 17]);
+        assert_eq!(by_chars, vec![0, 3, 7]);
+        assert_eq!(RollingHash::char_to_byte_offsets(txt, &by_chars), by_bytes);
+    }
+
+    #[test]
+    fn test_from_str_matches_new() {
+        let s = "héllo wörld";
+        let a = RollingHash::from_str(s, 1_000);
+        let b = RollingHash::new(s.as_bytes(), 1_000);
+        assert_eq!(a.query(None, None), b.query(None, None));
+        assert_eq!(a.query(Some(2), Some(7)), b.query(Some(2), Some(7)));
+    }
+
+    #[test]
+    fn test_from_chars_distinguishes_multibyte() {
+        let a = "aéb".chars().collect::<Vec<_>>();
+        let b = "aèb".chars().collect::<Vec<_>>();
+        let (ha, hb) = (
+            RollingHash::from_chars(&a, 1_000),
+            RollingHash::from_chars(&b, 1_000),
+        );
+        assert_ne!(ha.query(None, None), hb.query(None, None));
+        assert_eq!(ha.query(Some(0), Some(1)), hb.query(Some(0), Some(1)));
+        assert_eq!(ha.query(Some(2), Some(3)), hb.query(Some(2), Some(3)));
+        assert_eq!(
+            ha.query(None, None),
+            RollingHash::from_str_chars("aéb", 1_000).query(None, None)
+        );
+    }
+
+    #[test]
+    fn test_char_to_byte_offsets_accepts_end() {
+        let txt = "aé";
+        assert_eq!(
+            RollingHash::char_to_byte_offsets(txt, &[0, 1, 2]),
+            vec![0, 1, 3]
+        );
+    }
+
+    #[test]
+    fn test_find_any_matches_find_all() {
+        let base = 1_000_003;
+
+        let txt = RollingHash::new("ABABBABABABBABA".as_bytes(), base);
+        let ptns = ["ABA", "B", "BB", "ABA", "ZZ", "ABABBABABABBABAB", "BABA"]
+            .iter()
+            .map(|p| RollingHash::new(p.as_bytes(), base))
+            .collect::<Vec<_>>();
+        let found = txt.find_any(&ptns);
+        for (ptn, indices) in ptns.iter().zip(found.iter()) {
+            assert_eq!(txt.find_all(ptn).unwrap_or_default(), *indices);
+        }
+        assert_eq!(
+            txt.contains_which(&ptns),
+            vec![true, true, true, true, false, false, true]
+        );
+    }
 }
@@ -0,0 +1,125 @@
+use cargo_snippet::snippet;
+
+#[snippet("trie")]
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    prefix_count: usize,
+    word_count: usize,
+}
+
+#[snippet("trie")]
+/// Trie of byte strings.
+///
+/// Nodes are stored in a vector and refer their children by indices.
+/// The root is `nodes[0]`.
+#[derive(Debug, Clone)]
+pub struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+#[snippet("trie")]
+impl Default for Trie {
+    fn default() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+}
+
+#[snippet("trie")]
+impl Trie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn child(&self, node: usize, c: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|&&(k, _)| k == c)
+            .map(|&(_, v)| v)
+    }
+
+    /// Index of the node reached by following `word` from the root.
+    fn find(&self, word: &[u8]) -> Option<usize> {
+        let mut node = 0;
+        for &c in word {
+            node = self.child(node, c)?;
+        }
+        Some(node)
+    }
+
+    pub fn insert(&mut self, word: &[u8]) {
+        let mut node = 0;
+        self.nodes[node].prefix_count += 1;
+        for &c in word {
+            node = match self.child(node, c) {
+                Some(next) => next,
+                None => {
+                    let next = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[node].children.push((c, next));
+                    next
+                }
+            };
+            self.nodes[node].prefix_count += 1;
+        }
+        self.nodes[node].word_count += 1;
+    }
+
+    pub fn contains(&self, word: &[u8]) -> bool {
+        self.count_word(word) > 0
+    }
+
+    /// Number of inserted words which start with `prefix`.
+    pub fn count_prefix(&self, prefix: &[u8]) -> usize {
+        self.find(prefix).map_or(0, |i| self.nodes[i].prefix_count)
+    }
+
+    /// Number of inserted words which equal `word`.
+    pub fn count_word(&self, word: &[u8]) -> usize {
+        self.find(word).map_or(0, |i| self.nodes[i].word_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Trie {
+        let mut t = Trie::new();
+        for w in &["apple", "app", "apply", "banana", "app", "band"] {
+            t.insert(w.as_bytes());
+        }
+        t
+    }
+
+    #[test]
+    fn test_count_prefix() {
+        let t = sample();
+        assert_eq!(t.count_prefix(b""), 6);
+        assert_eq!(t.count_prefix(b"app"), 4);
+        assert_eq!(t.count_prefix(b"appl"), 2);
+        assert_eq!(t.count_prefix(b"ban"), 2);
+        assert_eq!(t.count_prefix(b"bana"), 1);
+    }
+
+    #[test]
+    fn test_count_word() {
+        let t = sample();
+        assert_eq!(t.count_word(b"app"), 2);
+        assert_eq!(t.count_word(b"apple"), 1);
+        assert_eq!(t.count_word(b"appl"), 0);
+        assert!(t.contains(b"band"));
+        assert!(!t.contains(b"ban"));
+    }
+
+    #[test]
+    fn test_missing_prefix() {
+        let t = sample();
+        assert_eq!(t.count_prefix(b"c"), 0);
+        assert_eq!(t.count_prefix(b"apples"), 0);
+        assert_eq!(t.count_word(b"cherry"), 0);
+    }
+}
@@ -34,9 +34,365 @@ pub fn get_z_array<T: PartialEq>(s: &[T]) -> Vec<usize> {
     z
 }
 
+#[snippet("z_algorithm")]
+/// Z-array in the other common convention where `z[0] = n`.
+pub fn get_z_array_full<T: PartialEq>(s: &[T]) -> Vec<usize> {
+    let mut z = get_z_array(s);
+    if let Some(z0) = z.first_mut() {
+        *z0 = s.len();
+    }
+    z
+}
+
+#[snippet("z_algorithm")]
+/// Z-arrays as methods of slices, e.g. `s.z_array()`.
+pub trait ZExt {
+    /// Same as `get_z_array`, where `z[0] = 0`.
+    fn z_array(&self) -> Vec<usize>;
+    /// Same as `get_z_array_full`, where `z[0] = n`.
+    fn z_array_full(&self) -> Vec<usize>;
+}
+
+#[snippet("z_algorithm")]
+impl<T: PartialEq> ZExt for [T] {
+    fn z_array(&self) -> Vec<usize> {
+        get_z_array(self)
+    }
+
+    fn z_array_full(&self) -> Vec<usize> {
+        get_z_array_full(self)
+    }
+}
+
+#[snippet("z_find", include = "z_algorithm")]
+/// Calls `f(i, len)` in increasing order of `i` in `0..=n` until `f` returns `false`,
+/// where `len` is the length of the longest common prefix of `&text[i..]` and `pattern`,
+/// with time-complexity `O(n + m)`.
+///
+/// Instead of concatenating `pattern` and `text` with a separator,
+/// `text` is streamed against the Z-array of `pattern`.
+fn z_scan<T: PartialEq, F: FnMut(usize, usize) -> bool>(text: &[T], pattern: &[T], mut f: F) {
+    let (n, m) = (text.len(), pattern.len());
+    let z = get_z_array(pattern);
+    // Window [left, right) of `text` which matches a prefix of `pattern`.
+    let (mut left, mut right) = (0, 0);
+    for i in 0..=n {
+        let mut len = if i < right {
+            z[i - left].min(right - i)
+        } else {
+            0
+        };
+        while len < m && i + len < n && pattern[len] == text[i + len] {
+            len += 1;
+        }
+        if i + len > right {
+            left = i;
+            right = i + len;
+        }
+        if !f(i, len) {
+            return;
+        }
+    }
+}
+
+#[snippet("z_find", include = "z_algorithm")]
+/// All starting indices of `pattern` in `text` with time-complexity `O(n + m)`.
+/// Empty `pattern` matches at every index in `0..=n`.
+pub fn z_find_all<T: PartialEq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    let mut res = vec![];
+    z_scan(text, pattern, |i, len| {
+        if len == pattern.len() {
+            res.push(i);
+        }
+        true
+    });
+    res
+}
+
+#[snippet("z_find", include = "z_algorithm")]
+/// First starting index of `pattern` in `text` with time-complexity `O(n + m)`.
+pub fn z_find_first<T: PartialEq>(text: &[T], pattern: &[T]) -> Option<usize> {
+    let mut res = None;
+    z_scan(text, pattern, |i, len| {
+        if len == pattern.len() {
+            res = Some(i);
+        }
+        res.is_none()
+    });
+    res
+}
+
+#[snippet("z_find", include = "z_algorithm")]
+/// `res[i]` = Length of the longest common prefix of `&t[i..]` and `s`
+/// with time-complexity `O(|s| + |t|)`, without concatenating them with a separator.
+pub fn z_cross<T: PartialEq>(s: &[T], t: &[T]) -> Vec<usize> {
+    let mut res = Vec::with_capacity(t.len());
+    z_scan(t, s, |i, len| {
+        if i < t.len() {
+            res.push(len);
+        }
+        true
+    });
+    res
+}
+
+#[snippet("z_array", include = "z_algorithm")]
+/// Z-array of a slice `s` of length `n` with documented conventions.
+///
+/// Unlike `get_z_array`, `z[0]` is regarded as `n` since the whole `s` is its own prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZArray {
+    z: Vec<usize>,
+}
+
+#[snippet("z_array", include = "z_algorithm")]
+impl ZArray {
+    pub fn new<T: PartialEq>(s: &[T]) -> Self {
+        Self {
+            z: get_z_array_full(s),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.z.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.z.is_empty()
+    }
+
+    /// Length of the longest common prefix of `s` and `s[i..]`.
+    pub fn lcp_with_prefix(&self, i: usize) -> usize {
+        self.z[i]
+    }
+
+    /// `(i, z[i])` with the largest `z[i]` for `i > 0`, the leftmost one if tied.
+    /// `None` if no proper suffix shares a nonempty prefix with `s`.
+    pub fn longest_prefix_occurrence(&self) -> Option<(usize, usize)> {
+        let mut res: Option<(usize, usize)> = None;
+        for (i, &len) in self.z.iter().enumerate().skip(1) {
+            let better = match res {
+                Some((_, best)) => len > best,
+                None => len > 0,
+            };
+            if better {
+                res = Some((i, len));
+            }
+        }
+        res
+    }
+
+    /// All `i` in increasing order where the prefix of `s` of length `len` occurs.
+    pub fn prefix_occurrences(&self, len: usize) -> Vec<usize> {
+        (0..self.len()).filter(|&i| self.z[i] >= len).collect()
+    }
+}
+
+#[snippet("z_period", include = "z_algorithm")]
+/// All `p` in increasing order such that `s[i] == s[i + p]` for every valid `i`,
+/// using that `p` is a period iff `p + z[p] >= n`. `s.len()` is included unless `s` is empty.
+pub fn all_periods<T: PartialEq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let z = get_z_array(s);
+    (1..=n).filter(|&p| p == n || p + z[p] >= n).collect()
+}
+
+#[snippet("z_period", include = "z_algorithm")]
+/// Smallest period of `s`, which is `0` for empty `s`.
+pub fn smallest_period_z<T: PartialEq>(s: &[T]) -> usize {
+    all_periods(s).first().cloned().unwrap_or(0)
+}
+
+#[snippet("z_period", include = "z_algorithm")]
+/// `(p, k)` with the smallest `p` such that `s` is `&s[..p]` repeated `k >= 2` times.
+pub fn is_power_of_smaller_string<T: PartialEq>(s: &[T]) -> Option<(usize, usize)> {
+    let n = s.len();
+    all_periods(s)
+        .into_iter()
+        .find(|&p| p < n && n % p == 0)
+        .map(|p| (p, n / p))
+}
+
+#[snippet("z_failure_conversion", include = "z_algorithm")]
+/// Failure function in the convention of `KnuthMorrisPratt::failure_function`,
+/// i.e. `fail[j]` is the longest border of the prefix of length `j`,
+/// from the Z-array `z` (with `z[0] == 0`) with time-complexity `O(n)`.
+pub fn z_to_failure(z: &[usize]) -> Vec<usize> {
+    let n = z.len();
+    let mut fail = vec![0; n + 1];
+    for i in 1..n {
+        // Prefixes ending inside the Z-box of `i` have borders starting at `i`.
+        // Once one is already set, the rest are set by a longer Z-box on the left.
+        for j in (0..z[i]).rev() {
+            if fail[i + j + 1] > 0 {
+                break;
+            }
+            fail[i + j + 1] = j + 1;
+        }
+    }
+    fail
+}
+
+#[snippet("z_failure_conversion", include = "z_algorithm")]
+/// Z-array (with `z[0] == 0`) from the failure function `fail` of length `n + 1`
+/// with time-complexity `O(n)`.
+pub fn failure_to_z(fail: &[usize]) -> Vec<usize> {
+    let n = fail.len().saturating_sub(1);
+    let mut z = vec![0; n];
+    for i in 2..=n {
+        if fail[i] > 0 {
+            z[i - fail[i]] = fail[i];
+        }
+    }
+    // Only the longest Z-box is known at each end position so far;
+    // fill in the rest by copying from the prefix.
+    let mut i = 1;
+    while i < n {
+        let mut t = i;
+        if z[i] > 0 {
+            for j in 1..z[i] {
+                if z[i + j] > z[j] {
+                    break;
+                }
+                z[i + j] = z[j].min(z[i] - j);
+                t = i + j;
+            }
+        }
+        i = t + 1;
+    }
+    z
+}
+
+#[snippet("z_failure_conversion", include = "z_algorithm")]
+/// Tests if `z` is the Z-array (with `z[0] == 0`) of some sequence.
+pub fn is_valid_z_array(z: &[usize]) -> bool {
+    let n = z.len();
+    if n == 0 {
+        return true;
+    }
+    if z[0] != 0 || (1..n).any(|i| i + z[i] > n) {
+        return false;
+    }
+    // Copy inside the Z-box reaching the farthest, and use a fresh value elsewhere.
+    let mut s = vec![0; n];
+    let (mut left, mut right) = (0, 0);
+    for i in 1..n {
+        if i + z[i] > right {
+            left = i;
+            right = i + z[i];
+        }
+        s[i] = if i < right { s[i - left] } else { i };
+    }
+    get_z_array(&s) == z
+}
+
+#[snippet("z_failure_conversion", include = "z_algorithm")]
+/// Tests if `fail` is the failure function of some sequence.
+pub fn is_valid_failure_function(fail: &[usize]) -> bool {
+    if fail.is_empty() || fail[0] != 0 {
+        return false;
+    }
+    let n = fail.len() - 1;
+    if (1..=n).any(|j| fail[j] >= j) {
+        return false;
+    }
+    // Copy the value which the border requires, and use a fresh value elsewhere.
+    let mut s = vec![0; n];
+    for j in 1..=n {
+        s[j - 1] = if fail[j] > 0 { s[fail[j] - 1] } else { j };
+    }
+    z_to_failure(&get_z_array(&s)) == fail
+}
+
+#[snippet("z_overlap", include = "z_find")]
+/// Length of the longest suffix of `s` which is also a prefix of `t`
+/// with time-complexity `O(|s| + |t|)`.
+pub fn max_overlap<T: PartialEq>(s: &[T], t: &[T]) -> usize {
+    let mut res = 0;
+    z_scan(s, t, |i, len| {
+        if i + len == s.len() {
+            res = len;
+            false
+        } else {
+            true
+        }
+    });
+    res
+}
+
+#[snippet("z_overlap", include = "z_find")]
+/// Shortest sequence starting with `s` and ending with `t`
+/// which overlaps them by `max_overlap(s, t)`.
+pub fn shortest_superpattern<T: PartialEq + Clone>(s: &[T], t: &[T]) -> Vec<T> {
+    let k = max_overlap(s, t);
+    let mut res = s.to_vec();
+    res.extend_from_slice(&t[k..]);
+    res
+}
+
+#[snippet("z_overlap", include = "z_find")]
+/// `(i, block_len, repeat_count)` for each position `i` such that `&s[i..]` starts with
+/// `repeat_count` copies of a block of length `block_len`, with time-complexity `O(n^2)`.
+///
+/// Among blocks repeated at least twice, the one covering the longest prefix of `&s[i..]`
+/// is chosen, with ties broken by shorter `block_len`.
+/// `(i, 1, 1)` if `&s[i..]` starts with no repetition.
+pub fn repetition_structure<T: PartialEq>(s: &[T]) -> Vec<(usize, usize, usize)> {
+    (0..s.len())
+        .map(|i| {
+            let z = get_z_array(&s[i..]);
+            let mut res = (i, 1, 1);
+            for (len, &lcp) in z.iter().enumerate().skip(1) {
+                let count = lcp / len + 1;
+                if count >= 2 && len * count > res.1 * res.2 {
+                    res = (i, len, count);
+                }
+            }
+            res
+        })
+        .collect()
+}
+
+#[snippet("z_one_mismatch", include = "z_find")]
+/// All `(i, mismatch)` in increasing order of `i` such that `&text[i..i + m]` differs from
+/// `pattern` in at most one position, with time-complexity `O(n + m)`.
+///
+/// `mismatch` is the offset of the differing position in `pattern`, or `None` for exact matches.
+/// Matching prefixes are computed by streaming `text` against `pattern`,
+/// and matching suffixes likewise on the reversed sequences.
+pub fn find_all_with_one_mismatch<T: PartialEq>(
+    text: &[T],
+    pattern: &[T],
+) -> Vec<(usize, Option<usize>)> {
+    let (n, m) = (text.len(), pattern.len());
+    if m > n {
+        return vec![];
+    }
+    if m == 0 {
+        return (0..=n).map(|i| (i, None)).collect();
+    }
+    let prefix = z_cross(pattern, text);
+    let suffix = z_cross(
+        &pattern.iter().rev().collect::<Vec<_>>(),
+        &text.iter().rev().collect::<Vec<_>>(),
+    );
+    (0..=n - m)
+        .filter_map(|i| {
+            if prefix[i] == m {
+                Some((i, None))
+            } else if prefix[i] + suffix[n - m - i] + 1 >= m {
+                Some((i, Some(prefix[i])))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::XorShift;
 
     #[test]
     fn test_z_algorithm() {
@@ -44,6 +400,32 @@ mod tests {
         assert_eq!(get_z_array(&pattern), vec![0, 1, 0, 0, 3, 1, 0, 2, 1, 0]);
     }
 
+    #[test]
+    fn test_z_array_against_brute_force() {
+        let mut rng = XorShift::new();
+        for n in 0..=12 {
+            for _ in 0..50 {
+                let s = (0..n).map(|_| rng.below(3)).collect::<Vec<_>>();
+                let z = get_z_array(&s);
+                let z_full = get_z_array_full(&s);
+                for i in 0..n {
+                    let lcp = s[i..].iter().zip(&s).take_while(|(a, b)| a == b).count();
+                    assert_eq!(z_full[i], lcp);
+                    assert_eq!(z[i], if i == 0 { 0 } else { lcp });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_z_ext() {
+        let s = "aabxaab".as_bytes();
+        assert_eq!(s.z_array(), vec![0, 1, 0, 0, 3, 1, 0]);
+        assert_eq!(s.z_array_full(), vec![7, 1, 0, 0, 3, 1, 0]);
+        assert_eq!([1, 1, 1].z_array_full(), vec![3, 2, 1]);
+        assert!(Vec::<u8>::new().z_array_full().is_empty());
+    }
+
     #[test]
     fn test_pattern_search() {
         let target = "ggccgggccctgtgaccacag";
@@ -59,4 +441,221 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(pos, vec![0, 5]);
     }
+
+    #[test]
+    fn test_z_find_matches_kmp() {
+        use crate::string::knuth_morris_pratt::KnuthMorrisPratt;
+        let cases = [
+            ("ggccgggccctgtgaccacag", "ggc"),
+            ("aaaaa", "aa"),
+            ("abababab", "abab"),
+            ("abc", "abcd"),
+            ("abc", "d"),
+            ("", "a"),
+        ];
+        for &(target, pattern) in &cases {
+            let (t, p) = (target.as_bytes(), pattern.as_bytes());
+            let kmp = KnuthMorrisPratt::new(t).find_all(p);
+            assert_eq!(z_find_all(t, p), kmp);
+            assert_eq!(z_find_first(t, p), kmp.first().cloned());
+        }
+    }
+
+    #[test]
+    fn test_z_find_without_sentinel() {
+        // Every `i64` value could appear, so no separator is available.
+        let (lo, hi) = (std::i64::MIN, std::i64::MAX);
+        let text = vec![lo, 0, hi, lo, 0, hi, lo, 0];
+        let pattern = vec![lo, 0];
+        assert_eq!(z_find_all(&text, &pattern), vec![0, 3, 6]);
+        assert_eq!(z_find_first(&text, &pattern), Some(0));
+        assert_eq!(z_find_first(&text, &[1]), None);
+        assert_eq!(z_find_all(&text[..2], &[]), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_z_array_struct() {
+        let z = ZArray::new(b"aabaaab");
+        let expected = [7, 1, 0, 2, 3, 1, 0];
+        for (i, &len) in expected.iter().enumerate() {
+            assert_eq!(z.lcp_with_prefix(i), len);
+        }
+        assert_eq!(z.longest_prefix_occurrence(), Some((4, 3)));
+        assert_eq!(z.prefix_occurrences(1), vec![0, 1, 3, 4, 5]);
+        assert_eq!(z.prefix_occurrences(2), vec![0, 3, 4]);
+        assert_eq!(z.prefix_occurrences(4), vec![0]);
+        assert_eq!(z.prefix_occurrences(8), vec![]);
+    }
+
+    #[test]
+    fn test_z_array_struct_trivial() {
+        let z = ZArray::new(b"abc");
+        assert_eq!(z.lcp_with_prefix(0), 3);
+        assert_eq!(z.longest_prefix_occurrence(), None);
+        assert!(ZArray::new::<u8>(&[]).is_empty());
+        assert_eq!(
+            ZArray::new(b"aaaa").longest_prefix_occurrence(),
+            Some((1, 3))
+        );
+    }
+
+    #[test]
+    fn test_all_periods() {
+        assert_eq!(all_periods(b"abaabaa"), vec![3, 6, 7]);
+        assert_eq!(all_periods(b"aaaa"), vec![1, 2, 3, 4]);
+        assert_eq!(all_periods(b"abcd"), vec![4]);
+        assert_eq!(all_periods::<u8>(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_is_power_of_smaller_string() {
+        assert_eq!(is_power_of_smaller_string(b"abcabcabc"), Some((3, 3)));
+        assert_eq!(is_power_of_smaller_string(b"aaaa"), Some((1, 4)));
+        assert_eq!(is_power_of_smaller_string(b"abaabaa"), None);
+        assert_eq!(is_power_of_smaller_string(b"a"), None);
+        assert_eq!(is_power_of_smaller_string::<u8>(&[]), None);
+    }
+
+    #[test]
+    fn test_smallest_period_agrees_with_kmp() {
+        use crate::string::knuth_morris_pratt::smallest_period;
+        let cases = [
+            "", "a", "abaabaa", "aaaa", "abcd", "abcabcab", "aabaab", "abab",
+        ];
+        for s in cases.iter() {
+            let s = s.as_bytes();
+            assert_eq!(smallest_period_z(s), smallest_period(s));
+        }
+    }
+
+    #[test]
+    fn test_z_failure_round_trip() {
+        use crate::string::knuth_morris_pratt::KnuthMorrisPratt;
+        let cases = [
+            "",
+            "a",
+            "aaaa",
+            "abaabaa",
+            "aabxaab",
+            "abcabcab",
+            "abacabadabacaba",
+        ];
+        for s in cases.iter() {
+            let s = s.as_bytes();
+            let z = get_z_array(s);
+            let fail = KnuthMorrisPratt::failure_function(s);
+            assert_eq!(z_to_failure(&z), fail);
+            assert_eq!(failure_to_z(&fail), z);
+            assert!(is_valid_z_array(&z));
+            assert!(is_valid_failure_function(&fail));
+        }
+    }
+
+    #[test]
+    fn test_invalid_arrays() {
+        // z[1] = 2 requires s[2] == s[1] == s[0], contradicting z[2] = 0.
+        assert!(!is_valid_z_array(&[0, 2, 0]));
+        assert!(is_valid_z_array(&[0, 0, 1]));
+        // Z-box exceeding the end
+        assert!(!is_valid_z_array(&[0, 3, 0]));
+        assert!(!is_valid_z_array(&[3, 0, 0]));
+        // Border may grow by at most one.
+        assert!(!is_valid_failure_function(&[0, 0, 1, 3]));
+        assert!(is_valid_failure_function(&[0, 0, 0, 1, 2]));
+        // fail[5] = 2 requires s[3] == s[0], contradicting fail[4] = 0.
+        assert!(!is_valid_failure_function(&[0, 0, 0, 1, 0, 2]));
+        assert!(!is_valid_failure_function(&[]));
+    }
+
+    #[test]
+    fn test_max_overlap() {
+        assert_eq!(max_overlap(b"abc", b"def"), 0);
+        assert_eq!(max_overlap(b"abcab", b"abd"), 2);
+        assert_eq!(max_overlap(b"aaaa", b"aaab"), 3);
+        assert_eq!(max_overlap(b"xab", b"ab"), 2);
+        assert_eq!(max_overlap(b"ab", b"abcd"), 2);
+        assert_eq!(max_overlap(b"", b"ab"), 0);
+        assert_eq!(max_overlap(b"ab", b""), 0);
+    }
+
+    #[test]
+    fn test_shortest_superpattern() {
+        assert_eq!(shortest_superpattern(b"abcab", b"abd"), b"abcabd".to_vec());
+        assert_eq!(shortest_superpattern(b"abc", b"def"), b"abcdef".to_vec());
+        assert_eq!(shortest_superpattern(b"ab", b"abcd"), b"abcd".to_vec());
+    }
+
+    #[test]
+    fn test_repetition_structure() {
+        assert_eq!(
+            repetition_structure(b"aabaabc"),
+            vec![
+                (0, 3, 2),
+                (1, 1, 1),
+                (2, 1, 1),
+                (3, 1, 2),
+                (4, 1, 1),
+                (5, 1, 1),
+                (6, 1, 1)
+            ]
+        );
+        assert_eq!(repetition_structure(b"abababab")[0], (0, 2, 4));
+    }
+
+    #[test]
+    fn test_find_all_with_one_mismatch() {
+        let text = b"abcdabce";
+        assert_eq!(
+            find_all_with_one_mismatch(text, b"abcd"),
+            vec![(0, None), (4, Some(3))]
+        );
+        assert_eq!(
+            find_all_with_one_mismatch(text, b"xbcd"),
+            vec![(0, Some(0))]
+        );
+        assert_eq!(
+            find_all_with_one_mismatch(text, b"dxbc"),
+            vec![(3, Some(1))]
+        );
+        assert_eq!(find_all_with_one_mismatch(text, b"xxcd"), vec![]);
+        assert_eq!(find_all_with_one_mismatch(b"ab", b"abc"), vec![]);
+    }
+
+    #[test]
+    fn test_find_all_with_one_mismatch_brute_force() {
+        let text = b"aabababbaababaaab";
+        for pattern in [&b"aba"[..], b"bb", b"a", b"abab", b"babba"].iter() {
+            let m = pattern.len();
+            let expected = (0..=text.len() - m)
+                .filter_map(|i| {
+                    let diff = (0..m)
+                        .filter(|&j| text[i + j] != pattern[j])
+                        .collect::<Vec<_>>();
+                    match diff.len() {
+                        0 => Some((i, None)),
+                        1 => Some((i, Some(diff[0]))),
+                        _ => None,
+                    }
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(find_all_with_one_mismatch(text, pattern), expected);
+        }
+    }
+
+    #[test]
+    fn test_z_cross_matches_concatenation() {
+        let cases = [
+            ("ggc", "ggccgggccctgtgaccacag"),
+            ("aab", "aaabaabaa"),
+            ("abc", ""),
+            ("", "abc"),
+            ("abacaba", "abacab"),
+        ];
+        for &(s, t) in &cases {
+            let (s, t) = (s.as_bytes(), t.as_bytes());
+            let concat = [s, &[0], t].concat();
+            let expected = get_z_array(&concat)[s.len() + 1..].to_vec();
+            assert_eq!(z_cross(s, t), expected);
+        }
+    }
 }
@@ -0,0 +1,48 @@
+//! Helpers shared by unit tests.
+
+/// Xorshift pseudo random number generator for randomized tests.
+pub struct XorShift(u64);
+
+impl XorShift {
+    pub fn new() -> Self {
+        Self::with_seed(0)
+    }
+
+    /// Creates a generator whose sequence differs for each `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        let x = 88_172_645_463_325_252 ^ seed;
+        XorShift(if x == 0 { 88_172_645_463_325_252 } else { x })
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a value in [`0`, `n`).
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Returns `len` values in [`0`, `max`).
+pub fn random_array(rng: &mut XorShift, len: usize, max: u64) -> Vec<u64> {
+    (0..len).map(|_| rng.next_u64() % max).collect()
+}
+
+/// Returns edges `(p, v)` of a random tree on `n` nodes, where `p < v`.
+pub fn random_tree(rng: &mut XorShift, n: usize) -> Vec<(usize, usize)> {
+    (1..n).map(|v| (rng.below(v), v)).collect()
+}
+
+/// Returns adjacency lists of the undirected graph on `n` nodes with `edges`.
+pub fn to_adjacency(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut adj = vec![vec![]; n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    adj
+}